@@ -0,0 +1,58 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    cairo::Context,
+    components::{Canvas, Text, View, Window},
+    events::PointerEvent,
+    geometry::Size,
+    launch,
+    style::{Dimension, FlexDirection, Style},
+};
+use snafu::Whatever;
+use std::rc::Rc;
+
+fn app(cx: Scope) -> Element {
+    let points = use_state(&cx, Vec::<(f64, f64)>::new);
+    let drawn = points.get().clone();
+    cx.render(rsx!(Window {
+        title: "Canvas",
+        View {
+            layout: Style {
+                size: Size {
+                    width: Dimension::Percent(1.),
+                    height: Dimension::Percent(1.),
+                },
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            Text {
+                label: format!("{} points, click to add more", points.len()),
+            }
+            Canvas {
+                layout: Style {
+                    flex_grow: 1.,
+                    ..Default::default()
+                },
+                draw: Rc::new(move |context: &Context, width: i32, height: i32| {
+                    context.set_source_rgb(1., 1., 1.);
+                    context.rectangle(0., 0., width as f64, height as f64);
+                    let _ = context.fill();
+                    context.set_source_rgb(0.2, 0.4, 0.8);
+                    context.set_line_width(2.);
+                    for (x, y) in &drawn {
+                        context.line_to(*x, *y);
+                    }
+                    let _ = context.stroke();
+                }),
+                on_pointer_down: move |evt: PointerEvent| {
+                    let mut next = points.get().clone();
+                    next.push((evt.data.x, evt.data.y));
+                    points.set(next);
+                },
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.canvas")?;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{Button, Dropdown, Text, View, Window},
+    events::SelectEvent,
+    launch,
+    style::{FlexDirection, Style},
+};
+use snafu::Whatever;
+
+fn app(cx: Scope) -> Element {
+    let options = use_state(&cx, || vec!["Red".to_owned(), "Green".to_owned()]);
+    let selected = use_state(&cx, || Some(0));
+    cx.render(rsx!(Window {
+        title: "Dropdown",
+        View {
+            layout: Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            Dropdown {
+                options: options.get().clone(),
+                selected: **selected,
+                on_select: move |evt: SelectEvent| selected.set(evt.data.index),
+            }
+            // Prepending keeps the selected color selected, only its index changes
+            Button {
+                label: "Add a color".to_owned(),
+                on_press: move |_| {
+                    let mut colors = options.get().clone();
+                    colors.insert(0, format!("Color {}", colors.len() + 1));
+                    selected.set(selected.map(|index| index + 1));
+                    options.set(colors);
+                },
+            }
+            Text {
+                label: match **selected {
+                    Some(index) => format!("Selected {}", options[index]),
+                    None => "Nothing selected".to_owned(),
+                },
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.dropdown")?;
+    Ok(())
+}
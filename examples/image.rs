@@ -0,0 +1,52 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{Image, ImageSource, Text, View, Window},
+    geometry::Size,
+    launch,
+    style::{Dimension, FlexDirection, Style},
+};
+use snafu::Whatever;
+use std::path::PathBuf;
+
+fn app(cx: Scope) -> Element {
+    let path = cx.use_hook(|_| std::env::args_os().nth(1).map(PathBuf::from));
+    cx.render(rsx!(Window {
+        title: "Image",
+        View {
+            layout: Style {
+                size: Size {
+                    width: Dimension::Percent(1.),
+                    height: Dimension::Percent(1.),
+                },
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            Image {
+                source: ImageSource::Icon {
+                    name: "face-smile".to_owned(),
+                    size: 64,
+                },
+            }
+            match path {
+                // Scaled down to the width of the window, keeping its aspect ratio
+                Some(path) => rsx!(Image {
+                    source: ImageSource::File(path.clone()),
+                    layout: Style {
+                        size: Size {
+                            width: Dimension::Percent(1.),
+                            height: Dimension::Auto,
+                        },
+                        ..Default::default()
+                    },
+                }),
+                None => rsx!(Text {
+                    label: "Pass the path of an image to show it".to_owned(),
+                }),
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.image")?;
+    Ok(())
+}
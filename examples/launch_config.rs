@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{Text, View, Window},
+    launch_with_config, LaunchConfig,
+};
+use snafu::Whatever;
+
+struct AppProps {
+    greeting: String,
+}
+
+fn app(cx: Scope<AppProps>) -> Element {
+    cx.render(rsx!(Window {
+        title: "Launch config",
+        View {
+            Text {
+                label: cx.props.greeting.clone(),
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch_with_config(
+        app,
+        LaunchConfig::new("org.dioxus-gtk.launch_config")
+            .with_default_size(640, 480)
+            .with_css("label { font-size: 24px; }")
+            .with_props(AppProps {
+                greeting: "Hello from the launcher".to_owned(),
+            }),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{List, ListItems, ListRowProps, Text, View, Window},
+    geometry::Size,
+    launch,
+    style::{Dimension, FlexDirection, Style},
+};
+use snafu::Whatever;
+use std::rc::Rc;
+
+#[allow(non_snake_case)]
+fn LogRow(cx: Scope<ListRowProps>) -> Element {
+    let entries = cx.props.items.get::<Vec<String>>().unwrap();
+    cx.render(rsx!(Text {
+        label: entries[cx.props.index].clone(),
+    }))
+}
+
+fn app(cx: Scope) -> Element {
+    let entries = use_state(&cx, || {
+        Rc::new(
+            (0..100_000)
+                .map(|i| format!("Log entry {}", i))
+                .collect::<Vec<_>>(),
+        )
+    });
+    cx.render(rsx!(Window {
+        title: "List",
+        View {
+            layout: Style {
+                size: Size {
+                    width: Dimension::Percent(1.),
+                    height: Dimension::Percent(1.),
+                },
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            Text {
+                label: format!("{} entries", entries.len()),
+            }
+            List {
+                layout: Style {
+                    flex_grow: 1.,
+                    ..Default::default()
+                },
+                item_count: entries.len(),
+                items: ListItems::new(entries.get().clone()),
+                row: LogRow,
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.list")?;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{Button, Text, View, Window},
+    geometry::Size,
+    launch,
+    style::{AlignItems, Dimension, JustifyContent, Style},
+};
+use snafu::Whatever;
+
+fn app(cx: Scope) -> Element {
+    let show_tools = use_state(&cx, || false);
+    cx.render(rsx!(
+        Window {
+            title: "Main",
+            View {
+                layout: Style {
+                    size: Size {
+                        width: Dimension::Percent(1.),
+                        height: Dimension::Percent(1.),
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                }
+                Button {
+                    label: if *show_tools.current() { "Close tools".to_owned() } else { "Open tools".to_owned() },
+                    on_press: move |_| show_tools.modify(|c| !c),
+                }
+            }
+        }
+        show_tools.then(|| rsx!{
+            Window {
+                title: "Tools",
+                View {
+                    Text {
+                        label: "Tool window".to_owned(),
+                    }
+                }
+            }
+        })
+    ))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.multi_window")?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{Slider, SpinButton, Text, View, Window},
+    events::ValueChangeEvent,
+    launch,
+    style::{FlexDirection, Style},
+};
+use snafu::Whatever;
+
+fn app(cx: Scope) -> Element {
+    let volume = use_state(&cx, || 50.);
+    let threads = use_state(&cx, || 4.);
+    cx.render(rsx!(Window {
+        title: "Numeric inputs",
+        View {
+            layout: Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            Text {
+                label: format!("Volume {:.0}%, {} threads", volume, threads),
+            }
+            Slider {
+                min: 0.,
+                max: 100.,
+                step: 5.,
+                digits: 0,
+                value: **volume,
+                on_value_change: move |evt: ValueChangeEvent| volume.set(evt.data.value),
+            }
+            SpinButton {
+                min: 1.,
+                max: 64.,
+                value: **threads,
+                on_value_change: move |evt: ValueChangeEvent| threads.set(evt.data.value),
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.numeric")?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{LevelBar, ProgressBar, Spinner, View, Window},
+    launch,
+    style::{FlexDirection, Style},
+};
+use snafu::Whatever;
+use std::time::Duration;
+
+fn app(cx: Scope) -> Element {
+    let ticks = use_state(&cx, || 0u64);
+    use_future(&cx, (), |_| {
+        let ticks = ticks.clone();
+        async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                ticks.modify(|ticks| ticks + 1);
+            }
+        }
+    });
+    let fraction = (**ticks % 101) as f64 / 100.;
+    cx.render(rsx!(Window {
+        title: "Progress",
+        View {
+            layout: Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            ProgressBar {
+                fraction: fraction,
+                show_text: true,
+            }
+            ProgressBar {
+                pulse: **ticks,
+            }
+            Spinner {
+                spinning: fraction < 1.,
+            }
+            LevelBar {
+                value: fraction,
+                offsets: vec![("low".to_owned(), 0.3), ("high".to_owned(), 0.8)],
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.progress")?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{ScrollPolicy, ScrollView, Text, View, Window},
+    events::ScrollEvent,
+    geometry::Size,
+    launch,
+    style::{Dimension, FlexDirection, Style},
+};
+use snafu::Whatever;
+
+fn app(cx: Scope) -> Element {
+    let offset = use_state(&cx, || 0.);
+    cx.render(rsx!(Window {
+        title: "Scroll view",
+        View {
+            layout: Style {
+                size: Size {
+                    width: Dimension::Percent(1.),
+                    height: Dimension::Percent(1.),
+                },
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            Text {
+                label: format!("Scrolled to {:.0}", offset),
+            }
+            ScrollView {
+                layout: Style {
+                    flex_grow: 1.,
+                    ..Default::default()
+                },
+                horizontal_policy: ScrollPolicy::Never,
+                on_scroll: move |evt: ScrollEvent| offset.set(evt.data.y),
+                (0..100).map(|i| rsx!(Text {
+                    key: "{i}",
+                    label: format!("Row {}", i),
+                }))
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.scroll_view")?;
+    Ok(())
+}
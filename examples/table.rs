@@ -0,0 +1,100 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{ListItems, ListRowProps, SelectionMode, Table, TableColumn, Text, View, Window},
+    events::{SelectionEvent, SortChangeEvent},
+    geometry::Size,
+    launch,
+    style::{Dimension, FlexDirection, Style},
+};
+use snafu::Whatever;
+use std::rc::Rc;
+
+struct User {
+    name: String,
+    email: String,
+}
+
+#[allow(non_snake_case)]
+fn NameCell(cx: Scope<ListRowProps>) -> Element {
+    let users = cx.props.items.get::<Vec<User>>().unwrap();
+    cx.render(rsx!(Text {
+        label: users[cx.props.index].name.clone(),
+    }))
+}
+
+#[allow(non_snake_case)]
+fn EmailCell(cx: Scope<ListRowProps>) -> Element {
+    let users = cx.props.items.get::<Vec<User>>().unwrap();
+    cx.render(rsx!(Text {
+        label: users[cx.props.index].email.clone(),
+    }))
+}
+
+fn app(cx: Scope) -> Element {
+    let users = use_state(&cx, || {
+        Rc::new(
+            (0..1000)
+                .map(|i| User {
+                    name: format!("User {}", i),
+                    email: format!("user{}@example.com", (i * 7919) % 1000),
+                })
+                .collect::<Vec<_>>(),
+        )
+    });
+    let selected = use_state(&cx, || 0);
+    cx.render(rsx!(Window {
+        title: "Table",
+        View {
+            layout: Style {
+                size: Size {
+                    width: Dimension::Percent(1.),
+                    height: Dimension::Percent(1.),
+                },
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            Text {
+                label: format!("{} selected", selected),
+            }
+            Table {
+                layout: Style {
+                    flex_grow: 1.,
+                    ..Default::default()
+                },
+                item_count: users.len(),
+                items: ListItems::new(users.get().clone()),
+                columns: vec![
+                    TableColumn::new("Name", NameCell).with_sortable(true),
+                    TableColumn::new("Email", EmailCell)
+                        .with_sortable(true)
+                        .with_expand(true),
+                ],
+                selection_mode: SelectionMode::Multiple,
+                on_select: move |evt: SelectionEvent| selected.set(evt.data.rows.len()),
+                on_sort_change: move |evt: SortChangeEvent| {
+                    if let Some(column) = evt.data.column {
+                        let mut sorted = users
+                            .iter()
+                            .map(|user| User {
+                                name: user.name.clone(),
+                                email: user.email.clone(),
+                            })
+                            .collect::<Vec<_>>();
+                        sorted.sort_by(|a, b| match column {
+                            0 => a.name.cmp(&b.name),
+                            _ => a.email.cmp(&b.email),
+                        });
+                        if !evt.data.ascending {
+                            sorted.reverse();
+                        }
+                        users.set(Rc::new(sorted));
+                    }
+                },
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.table")?;
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{Button, Text, TextArea, View, Window, WrapMode},
+    events::{CursorMoveEvent, TextChangeEvent},
+    launch,
+    style::{FlexDirection, Style},
+};
+use snafu::Whatever;
+
+fn app(cx: Scope) -> Element {
+    let note = use_state(&cx, String::new);
+    let cursor = use_state(&cx, || (0, 0));
+    cx.render(rsx!(Window {
+        title: "Notes",
+        View {
+            layout: Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            TextArea {
+                value: note.get().clone(),
+                wrap: WrapMode::WordChar,
+                monospace: true,
+                on_text_change: move |evt: TextChangeEvent| note.set(evt.data.value.clone()),
+                on_cursor_move: move |evt: CursorMoveEvent| {
+                    cursor.set((evt.data.line, evt.data.column))
+                },
+            }
+            Text {
+                label: format!("Line {}, column {}", cursor.0 + 1, cursor.1 + 1),
+            }
+            Button {
+                label: "Clear".to_owned(),
+                on_press: move |_| note.set(String::new()),
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.text_area")?;
+    Ok(())
+}
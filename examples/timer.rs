@@ -0,0 +1,32 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{Text, View, Window},
+    launch,
+};
+use snafu::Whatever;
+use std::time::Duration;
+
+fn app(cx: Scope) -> Element {
+    let seconds = use_state(&cx, || 0);
+    use_future(&cx, (), |_| {
+        let seconds = seconds.clone();
+        async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                seconds.modify(|s| s + 1);
+            }
+        }
+    });
+    cx.render(rsx!(Window {
+        title: "Timer",
+        View {
+            Text {
+                label: format!("Running for {} seconds", seconds),
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.timer")?;
+    Ok(())
+}
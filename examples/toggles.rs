@@ -0,0 +1,57 @@
+use dioxus::prelude::*;
+use gtk_platform::{
+    components::{CheckBox, Switch, Text, ToggleButton, View, Window},
+    events::ToggleEvent,
+    launch,
+    style::{FlexDirection, Style},
+};
+use snafu::Whatever;
+
+fn app(cx: Scope) -> Element {
+    let notifications = use_state(&cx, || true);
+    let dark_mode = use_state(&cx, || false);
+    let bold = use_state(&cx, || false);
+    let size = use_state(&cx, || "Medium");
+    cx.render(rsx!(Window {
+        title: "Toggles",
+        View {
+            layout: Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+            CheckBox {
+                label: "Notifications".to_owned(),
+                checked: **notifications,
+                on_toggle: move |evt: ToggleEvent| notifications.set(evt.data.active),
+            }
+            Switch {
+                active: **dark_mode,
+                on_toggle: move |evt: ToggleEvent| dark_mode.set(evt.data.active),
+            }
+            ToggleButton {
+                label: "Bold".to_owned(),
+                active: **bold,
+                on_toggle: move |evt: ToggleEvent| bold.set(evt.data.active),
+            }
+            ["Small", "Medium", "Large"].into_iter().map(|option| rsx!(CheckBox {
+                key: "{option}",
+                label: option.to_owned(),
+                group: "size".to_owned(),
+                checked: **size == option,
+                on_toggle: move |evt: ToggleEvent| if evt.data.active {
+                    size.set(option);
+                },
+            }))
+            Text {
+                label: format!(
+                    "Notifications {}, dark mode {}, bold {}, size {}",
+                    notifications, dark_mode, bold, size
+                ),
+            }
+        }
+    }))
+}
+pub fn main() -> Result<(), Whatever> {
+    launch(app, "org.dioxus-gtk.toggles")?;
+    Ok(())
+}
@@ -3,9 +3,33 @@
 use bumpalo::{boxed::Box as BumpBox, collections::Vec as BumpVec};
 use dioxus::prelude::Props;
 use dioxus_core::{exports::bumpalo, prelude::*, IntoVNode};
+use gtk::cairo::Context;
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+use std::rc::Rc;
 use taffy::style::Style;
 
-use crate::events::{PressData, PressEvent, TextChangeData, TextChangeEvent};
+use crate::events::{
+    CursorMoveData, CursorMoveEvent, PointerData, PointerEvent, PressData, PressEvent, ScrollData,
+    ScrollEvent, SelectData, SelectEvent, SelectionData, SelectionEvent, SortChangeData,
+    SortChangeEvent, TextChangeData, TextChangeEvent, ToggleData, ToggleEvent, ValueChangeData,
+    ValueChangeEvent,
+};
+use crate::list::ListSource;
+use crate::values::use_value;
+
+/// Writes nothing for `None`, so an optional prop keeps its attribute's position.
+struct OptionalValue<T>(Option<T>);
+
+impl<T: Display> Display for OptionalValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Some(value) => value.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
 
 #[derive(Props)]
 pub struct ViewProps<'a> {
@@ -14,19 +38,15 @@ pub struct ViewProps<'a> {
 }
 
 pub fn View<'a>(cx: Scope<'a, ViewProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
     cx.render(LazyNodes::new(move |f| {
         let mut children: BumpVec<VNode> = BumpVec::new_in(f.bump());
         if let Some(ref node) = cx.props.children {
             children.push(node.into_vnode(f));
         }
         let mut attrs = BumpVec::new_in(f.bump());
-        if let Some(ref layout) = cx.props.layout {
-            attrs.push(f.attr(
-                "layout",
-                format_args!("{}", serde_json::to_string(layout).unwrap()),
-                None,
-                false,
-            ));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
         }
         f.raw_element(
             "gtk_box",
@@ -45,21 +65,132 @@ pub struct TextProps {
     layout: Option<Style>,
 }
 pub fn Text(cx: Scope<'_, TextProps>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
     cx.render(LazyNodes::new(move |f| {
         let mut attrs = BumpVec::new_in(f.bump());
         attrs.push(f.attr("text", format_args!("{}", cx.props.label), None, false));
-        if let Some(ref layout) = cx.props.layout {
-            attrs.push(f.attr(
-                "layout",
-                format_args!("{}", serde_json::to_string(layout).unwrap()),
-                None,
-                false,
-            ));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
         }
         f.raw_element("gtk_label", None, &[], attrs.into_bump_slice(), &[], None)
     }))
 }
 
+/// What an [`Image`] shows.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ImageSource {
+    File(PathBuf),
+    /// Path of an image in a registered GResource bundle
+    Resource(String),
+    /// Icon of the current icon theme, `size` pixels wide and high
+    Icon {
+        name: String,
+        size: i32,
+    },
+    /// Encoded image data, in any format GDK can load
+    Bytes(Rc<[u8]>),
+}
+
+#[derive(Props, PartialEq)]
+pub struct ImageProps {
+    source: ImageSource,
+    /// Letterboxes the image rather than stretching it to its allocation, defaults to `true`
+    keep_aspect: Option<bool>,
+    layout: Option<Style>,
+}
+pub fn Image(cx: Scope<'_, ImageProps>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    let source = use_value(&cx, Some(&cx.props.source));
+    cx.render(LazyNodes::new(move |f| {
+        let mut attrs = BumpVec::new_in(f.bump());
+        if let Some(source) = source {
+            attrs.push(f.attr("source", format_args!("{}", source), None, false));
+        }
+        if let Some(keep_aspect) = cx.props.keep_aspect {
+            attrs.push(f.attr("keep_aspect", format_args!("{}", keep_aspect), None, false));
+        }
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        f.raw_element("gtk_image", None, &[], attrs.into_bump_slice(), &[], None)
+    }))
+}
+
+/// Draws a [`Canvas`] with the width and height it was allocated.
+pub type DrawFn = Rc<dyn Fn(&Context, i32, i32)>;
+
+/// Compared by identity, the canvas is redrawn when it is given another closure.
+#[derive(Clone)]
+pub(crate) struct Draw(pub DrawFn);
+
+impl PartialEq for Draw {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::as_ptr(&self.0) as *const () == Rc::as_ptr(&other.0) as *const ()
+    }
+}
+
+#[derive(Props)]
+pub struct CanvasProps<'a> {
+    /// Called by GTK whenever the canvas needs to be drawn. A new closure, created by
+    /// rendering with other values, redraws it.
+    draw: DrawFn,
+    layout: Option<Style>,
+    #[props(default)]
+    on_pointer_down: EventHandler<'a, PointerEvent>,
+    #[props(default)]
+    on_pointer_up: EventHandler<'a, PointerEvent>,
+    #[props(default)]
+    on_pointer_move: EventHandler<'a, PointerEvent>,
+}
+pub fn Canvas<'a>(cx: Scope<'a, CanvasProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    let draw = use_value(&cx, Some(&Draw(cx.props.draw.clone())));
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(draw) = draw {
+            attrs.push(f.attr("draw", format_args!("{}", draw), None, false));
+        }
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        for (name, on_pointer) in [
+            ("pointer_down", &cx.props.on_pointer_down),
+            ("pointer_up", &cx.props.on_pointer_up),
+            ("pointer_move", &cx.props.on_pointer_move),
+        ] {
+            // Only handled events are listened to, so pointer moves don't wake the scheduler
+            // for nothing
+            if on_pointer.callback.borrow().is_none() {
+                continue;
+            }
+            // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+            // safety: this is essentially the same as calling Box::new() but manually
+            // The box is attached to the lifetime of the bumpalo allocator
+            let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+                let event = evt.downcast::<PointerData>().unwrap();
+                on_pointer.call(event);
+            });
+
+            let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+            let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+            listeners.push(f.listener(name, handler));
+        }
+        f.raw_element(
+            "gtk_canvas",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
 #[derive(Props)]
 pub struct ButtonProps<'a> {
     label: String,
@@ -67,17 +198,13 @@ pub struct ButtonProps<'a> {
     on_press: EventHandler<'a, PressEvent>,
 }
 pub fn Button<'a>(cx: Scope<'a, ButtonProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
     cx.render(LazyNodes::new(move |f| {
         let bump = &f.bump();
         let mut attrs = BumpVec::new_in(bump);
         attrs.push(f.attr("label", format_args!("{}", cx.props.label), None, false));
-        if let Some(ref layout) = cx.props.layout {
-            attrs.push(f.attr(
-                "layout",
-                format_args!("{}", serde_json::to_string(layout).unwrap()),
-                None,
-                false,
-            ));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
         }
         let mut listeners = BumpVec::new_in(bump);
 
@@ -105,25 +232,50 @@ pub fn Button<'a>(cx: Scope<'a, ButtonProps<'a>>) -> Element {
     }))
 }
 
+/// Decides whether a [`Window`] stays open when the user tries to close it. It is called
+/// while GTK handles the request, so unlike an event handler its result can veto the close.
+pub type CloseRequestFn = Rc<dyn Fn() -> bool>;
+
+/// Compared by identity, so the window only updates its handler when given another closure.
+#[derive(Clone)]
+pub(crate) struct CloseRequest(pub CloseRequestFn);
+
+impl PartialEq for CloseRequest {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::as_ptr(&self.0) as *const () == Rc::as_ptr(&other.0) as *const ()
+    }
+}
+
 #[derive(Props)]
 pub struct WindowProps<'a> {
     title: &'a str,
     children: Element<'a>,
     layout: Option<Style>,
+    /// Called when the user tries to close the window, which stays open if it returns `true`.
+    on_close_request: Option<CloseRequestFn>,
 }
 
 pub fn Window<'a>(cx: Scope<'a, WindowProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    let close_request = use_value(
+        &cx,
+        cx.props.on_close_request.clone().map(CloseRequest).as_ref(),
+    );
     cx.render(LazyNodes::new(move |f| {
-        let mut children: BumpVec<VNode> = BumpVec::new_in(f.bump());
+        let bump = &f.bump();
+        let mut children: BumpVec<VNode> = BumpVec::new_in(bump);
         if let Some(ref node) = cx.props.children {
             children.push(node.into_vnode(f));
         }
-        let mut attrs = dioxus::core::exports::bumpalo::collections::Vec::new_in(f.bump());
+        let mut attrs = dioxus::core::exports::bumpalo::collections::Vec::new_in(bump);
         attrs.push(f.attr("title", format_args!("{}", cx.props.title), None, false));
-        if let Some(ref layout) = cx.props.layout {
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        if let Some(close_request) = close_request {
             attrs.push(f.attr(
-                "layout",
-                format_args!("{}", serde_json::to_string(layout).unwrap()),
+                "close_request",
+                format_args!("{}", close_request),
                 None,
                 false,
             ));
@@ -147,6 +299,7 @@ pub struct TextFieldProps<'a> {
     on_text_change: EventHandler<'a, TextChangeEvent>,
 }
 pub fn TextField<'a>(cx: Scope<'a, TextFieldProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
     cx.render(LazyNodes::new(move |f| {
         let bump = &f.bump();
         let mut attrs = BumpVec::new_in(bump);
@@ -158,13 +311,8 @@ pub fn TextField<'a>(cx: Scope<'a, TextFieldProps<'a>>) -> Element {
                 false,
             ));
         }
-        if let Some(ref layout) = cx.props.layout {
-            attrs.push(f.attr(
-                "layout",
-                format_args!("{}", serde_json::to_string(layout).unwrap()),
-                None,
-                false,
-            ));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
         }
 
         attrs.push(f.attr("value", format_args!("{}", cx.props.value), None, false));
@@ -193,3 +341,825 @@ pub fn TextField<'a>(cx: Scope<'a, TextFieldProps<'a>>) -> Element {
         )
     }))
 }
+
+/// Where the lines of a [`TextArea`] are wrapped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WrapMode {
+    None,
+    Char,
+    Word,
+    /// Between words, or between characters for words longer than a line
+    WordChar,
+}
+
+impl WrapMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WrapMode::None => "none",
+            WrapMode::Char => "char",
+            WrapMode::Word => "word",
+            WrapMode::WordChar => "word_char",
+        }
+    }
+}
+
+#[derive(Props)]
+pub struct TextAreaProps<'a> {
+    value: String,
+    /// Defaults to [`WrapMode::None`]
+    wrap: Option<WrapMode>,
+    editable: Option<bool>,
+    monospace: Option<bool>,
+    layout: Option<Style>,
+    on_text_change: EventHandler<'a, TextChangeEvent>,
+    #[props(default)]
+    on_cursor_move: EventHandler<'a, CursorMoveEvent>,
+}
+pub fn TextArea<'a>(cx: Scope<'a, TextAreaProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        // Dioxus diffs attributes by position, so every one is sent even when it is unset
+        let wrap = cx.props.wrap.unwrap_or(WrapMode::None);
+        attrs.push(f.attr("wrap", format_args!("{}", wrap.as_str()), None, false));
+        let editable = cx.props.editable.unwrap_or(true);
+        attrs.push(f.attr("editable", format_args!("{}", editable), None, false));
+        let monospace = cx.props.monospace.unwrap_or(false);
+        attrs.push(f.attr("monospace", format_args!("{}", monospace), None, false));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        attrs.push(f.attr("value", format_args!("{}", cx.props.value), None, false));
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<TextChangeData>().unwrap();
+            cx.props.on_text_change.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("text_change", handler));
+
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<CursorMoveData>().unwrap();
+            cx.props.on_cursor_move.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("cursor_move", handler));
+        f.raw_element(
+            "gtk_text_area",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props)]
+pub struct CheckBoxProps<'a> {
+    label: Option<String>,
+    checked: bool,
+    /// Check boxes sharing a group are radio buttons, only one of them can be checked
+    group: Option<String>,
+    layout: Option<Style>,
+    on_toggle: EventHandler<'a, ToggleEvent>,
+}
+pub fn CheckBox<'a>(cx: Scope<'a, CheckBoxProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(ref label) = cx.props.label {
+            attrs.push(f.attr("label", format_args!("{}", label), None, false));
+        }
+        if let Some(ref group) = cx.props.group {
+            attrs.push(f.attr("group", format_args!("{}", group), None, false));
+        }
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        attrs.push(f.attr("active", format_args!("{}", cx.props.checked), None, false));
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<ToggleData>().unwrap();
+            cx.props.on_toggle.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("toggle", handler));
+        f.raw_element(
+            "gtk_check_box",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props)]
+pub struct SwitchProps<'a> {
+    active: bool,
+    layout: Option<Style>,
+    on_toggle: EventHandler<'a, ToggleEvent>,
+}
+pub fn Switch<'a>(cx: Scope<'a, SwitchProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        attrs.push(f.attr("active", format_args!("{}", cx.props.active), None, false));
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<ToggleData>().unwrap();
+            cx.props.on_toggle.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("toggle", handler));
+        f.raw_element(
+            "gtk_switch",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props)]
+pub struct ToggleButtonProps<'a> {
+    label: Option<String>,
+    active: bool,
+    layout: Option<Style>,
+    on_toggle: EventHandler<'a, ToggleEvent>,
+}
+pub fn ToggleButton<'a>(cx: Scope<'a, ToggleButtonProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(ref label) = cx.props.label {
+            attrs.push(f.attr("label", format_args!("{}", label), None, false));
+        }
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        attrs.push(f.attr("active", format_args!("{}", cx.props.active), None, false));
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<ToggleData>().unwrap();
+            cx.props.on_toggle.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("toggle", handler));
+        f.raw_element(
+            "gtk_toggle_button",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props)]
+pub struct SliderProps<'a> {
+    min: f64,
+    max: f64,
+    value: f64,
+    /// Defaults to 1
+    step: Option<f64>,
+    /// Shows the value with this many decimal places, which it is rounded to
+    digits: Option<u32>,
+    layout: Option<Style>,
+    on_value_change: EventHandler<'a, ValueChangeEvent>,
+}
+pub fn Slider<'a>(cx: Scope<'a, SliderProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        // The bounds go first, so the value is not clamped to the previous ones
+        attrs.push(f.attr("min", format_args!("{}", cx.props.min), None, false));
+        attrs.push(f.attr("max", format_args!("{}", cx.props.max), None, false));
+        // Sent even when unset, as dioxus diffs attributes by position. Without digits the
+        // value is not shown.
+        let step = cx.props.step.unwrap_or(1.);
+        attrs.push(f.attr("step", format_args!("{}", step), None, false));
+        attrs.push(f.attr(
+            "digits",
+            format_args!("{}", OptionalValue(cx.props.digits)),
+            None,
+            false,
+        ));
+        attrs.push(f.attr("value", format_args!("{}", cx.props.value), None, false));
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<ValueChangeData>().unwrap();
+            cx.props.on_value_change.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("value_change", handler));
+        f.raw_element(
+            "gtk_slider",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props)]
+pub struct SpinButtonProps<'a> {
+    min: f64,
+    max: f64,
+    value: f64,
+    /// Defaults to 1
+    step: Option<f64>,
+    /// Decimal places shown, defaults to 0
+    digits: Option<u32>,
+    layout: Option<Style>,
+    on_value_change: EventHandler<'a, ValueChangeEvent>,
+}
+pub fn SpinButton<'a>(cx: Scope<'a, SpinButtonProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        // The bounds go first, so the value is not clamped to the previous ones
+        attrs.push(f.attr("min", format_args!("{}", cx.props.min), None, false));
+        attrs.push(f.attr("max", format_args!("{}", cx.props.max), None, false));
+        let step = cx.props.step.unwrap_or(1.);
+        attrs.push(f.attr("step", format_args!("{}", step), None, false));
+        let digits = cx.props.digits.unwrap_or(0);
+        attrs.push(f.attr("digits", format_args!("{}", digits), None, false));
+        attrs.push(f.attr("value", format_args!("{}", cx.props.value), None, false));
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<ValueChangeData>().unwrap();
+            cx.props.on_value_change.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("value_change", handler));
+        f.raw_element(
+            "gtk_spin_button",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props)]
+pub struct DropdownProps<'a> {
+    options: Vec<String>,
+    /// Index of the selected option, nothing is selected if `None`
+    selected: Option<usize>,
+    layout: Option<Style>,
+    on_select: EventHandler<'a, SelectEvent>,
+}
+pub fn Dropdown<'a>(cx: Scope<'a, DropdownProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    let options = use_value(&cx, Some(&cx.props.options));
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        // The options go first, so the selection refers to the new ones
+        if let Some(options) = options {
+            attrs.push(f.attr("options", format_args!("{}", options), None, false));
+        }
+        if let Some(selected) = cx.props.selected {
+            attrs.push(f.attr("selected", format_args!("{}", selected), None, false));
+        }
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<SelectData>().unwrap();
+            cx.props.on_select.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("select", handler));
+        f.raw_element(
+            "gtk_dropdown",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props, PartialEq)]
+pub struct ProgressBarProps {
+    /// Completed part of the task, from 0 to 1
+    fraction: Option<f64>,
+    /// Moves the bar back and forth once every time the value increases, for tasks whose
+    /// progress is unknown
+    pulse: Option<u64>,
+    /// Shows the percentage, or `text` if it is given
+    show_text: Option<bool>,
+    /// An empty text shows the percentage as well
+    text: Option<String>,
+    layout: Option<Style>,
+}
+pub fn ProgressBar(cx: Scope<'_, ProgressBarProps>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let mut attrs = BumpVec::new_in(f.bump());
+        // Dioxus diffs attributes by position, so every one is sent even when it is unset
+        let fraction = cx.props.fraction.unwrap_or(0.);
+        attrs.push(f.attr("fraction", format_args!("{}", fraction), None, false));
+        attrs.push(f.attr(
+            "pulse",
+            format_args!("{}", OptionalValue(cx.props.pulse)),
+            None,
+            false,
+        ));
+        let show_text = cx.props.show_text.unwrap_or(false);
+        attrs.push(f.attr("show_text", format_args!("{}", show_text), None, false));
+        attrs.push(f.attr(
+            "text",
+            format_args!("{}", OptionalValue(cx.props.text.as_ref())),
+            None,
+            false,
+        ));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        f.raw_element(
+            "gtk_progress_bar",
+            None,
+            &[],
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+#[derive(Props, PartialEq)]
+pub struct SpinnerProps {
+    spinning: bool,
+    layout: Option<Style>,
+}
+pub fn Spinner(cx: Scope<'_, SpinnerProps>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let mut attrs = BumpVec::new_in(f.bump());
+        attrs.push(f.attr(
+            "spinning",
+            format_args!("{}", cx.props.spinning),
+            None,
+            false,
+        ));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        f.raw_element("gtk_spinner", None, &[], attrs.into_bump_slice(), &[], None)
+    }))
+}
+
+#[derive(Props, PartialEq)]
+pub struct LevelBarProps {
+    value: f64,
+    /// Defaults to 0. GTK only accepts bounds that are not negative, so lower ones are raised
+    /// to 0.
+    min: Option<f64>,
+    /// Defaults to 1
+    max: Option<f64>,
+    /// Named levels, styled through the `levelbar block.<name>` CSS selector once the value
+    /// reaches them
+    offsets: Option<Vec<(String, f64)>>,
+    layout: Option<Style>,
+}
+pub fn LevelBar(cx: Scope<'_, LevelBarProps>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    let offsets = use_value(&cx, cx.props.offsets.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let mut attrs = BumpVec::new_in(f.bump());
+        // The bounds go first, so the value is not clamped to the previous ones. Dioxus diffs
+        // attributes by position, so every one is sent even when it is unset.
+        let min = cx.props.min.unwrap_or(0.);
+        attrs.push(f.attr("min", format_args!("{}", min), None, false));
+        let max = cx.props.max.unwrap_or(1.);
+        attrs.push(f.attr("max", format_args!("{}", max), None, false));
+        attrs.push(f.attr(
+            "offsets",
+            format_args!("{}", OptionalValue(offsets)),
+            None,
+            false,
+        ));
+        attrs.push(f.attr("value", format_args!("{}", cx.props.value), None, false));
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        f.raw_element(
+            "gtk_level_bar",
+            None,
+            &[],
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
+
+/// When a scroll bar of a [`ScrollView`] is shown.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScrollPolicy {
+    Automatic,
+    Always,
+    Never,
+    External,
+}
+
+impl ScrollPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ScrollPolicy::Automatic => "automatic",
+            ScrollPolicy::Always => "always",
+            ScrollPolicy::Never => "never",
+            ScrollPolicy::External => "external",
+        }
+    }
+}
+
+#[derive(Props)]
+pub struct ScrollViewProps<'a> {
+    children: Element<'a>,
+    layout: Option<Style>,
+    horizontal_policy: Option<ScrollPolicy>,
+    vertical_policy: Option<ScrollPolicy>,
+    /// Scrolls to this offset whenever it changes
+    scroll_x: Option<f64>,
+    scroll_y: Option<f64>,
+    #[props(default)]
+    on_scroll: EventHandler<'a, ScrollEvent>,
+}
+
+pub fn ScrollView<'a>(cx: Scope<'a, ScrollViewProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut children: BumpVec<VNode> = BumpVec::new_in(bump);
+        if let Some(ref node) = cx.props.children {
+            children.push(node.into_vnode(f));
+        }
+        let mut attrs = BumpVec::new_in(bump);
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        // Dioxus diffs attributes by position, so every one is sent even when it is unset
+        let horizontal_policy = cx
+            .props
+            .horizontal_policy
+            .unwrap_or(ScrollPolicy::Automatic);
+        attrs.push(f.attr(
+            "horizontal_policy",
+            format_args!("{}", horizontal_policy.as_str()),
+            None,
+            false,
+        ));
+        let vertical_policy = cx.props.vertical_policy.unwrap_or(ScrollPolicy::Automatic);
+        attrs.push(f.attr(
+            "vertical_policy",
+            format_args!("{}", vertical_policy.as_str()),
+            None,
+            false,
+        ));
+        // An empty offset leaves the content where it is
+        attrs.push(f.attr(
+            "scroll_x",
+            format_args!("{}", OptionalValue(cx.props.scroll_x)),
+            None,
+            false,
+        ));
+        attrs.push(f.attr(
+            "scroll_y",
+            format_args!("{}", OptionalValue(cx.props.scroll_y)),
+            None,
+            false,
+        ));
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<ScrollData>().unwrap();
+            cx.props.on_scroll.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("scroll", handler));
+        f.raw_element(
+            "gtk_scroll_view",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            children.into_bump_slice(),
+            None,
+        )
+    }))
+}
+
+/// Data shared with the rows of a [`List`]. Rows are rendered by trees of their own, so they
+/// can't borrow from the component rendering the list.
+///
+/// Compared by identity, rows only render again when the data is replaced.
+#[derive(Clone)]
+pub struct ListItems(Rc<dyn Any>);
+
+impl ListItems {
+    pub fn new<T: 'static>(items: Rc<T>) -> Self {
+        ListItems(items)
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl Default for ListItems {
+    fn default() -> Self {
+        ListItems(Rc::new(()))
+    }
+}
+
+impl PartialEq for ListItems {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::as_ptr(&self.0) as *const () == Rc::as_ptr(&other.0) as *const ()
+    }
+}
+
+#[derive(Props, PartialEq)]
+pub struct ListRowProps {
+    pub index: usize,
+    pub items: ListItems,
+}
+
+#[derive(Props)]
+pub struct ListProps {
+    layout: Option<Style>,
+    item_count: usize,
+    /// Renders the row at `index`. Only rows in sight are rendered, so lists can be long.
+    row: Component<ListRowProps>,
+    #[props(default)]
+    items: ListItems,
+}
+
+impl PartialEq for ListProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.layout == other.layout
+            && self.item_count == other.item_count
+            && self.row as usize == other.row as usize
+            && self.items == other.items
+    }
+}
+
+pub fn List(cx: Scope<'_, ListProps>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    let source = use_value(
+        &cx,
+        Some(&ListSource {
+            row: cx.props.row,
+            items: cx.props.items.clone(),
+        }),
+    );
+    cx.render(LazyNodes::new(move |f| {
+        let mut attrs = BumpVec::new_in(f.bump());
+        attrs.push(f.attr(
+            "item_count",
+            format_args!("{}", cx.props.item_count),
+            None,
+            false,
+        ));
+        if let Some(source) = source {
+            attrs.push(f.attr("source", format_args!("{}", source), None, false));
+        }
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        f.raw_element("gtk_list", None, &[], attrs.into_bump_slice(), &[], None)
+    }))
+}
+
+/// A column of a [`Table`], whose cells are rendered like the rows of a [`List`].
+#[derive(Clone)]
+pub struct TableColumn {
+    pub title: String,
+    /// Makes the header clickable, the rows are sorted by the application on sort change
+    pub sortable: bool,
+    /// Gives the column a share of the table's spare width
+    pub expand: bool,
+    pub cell: Component<ListRowProps>,
+}
+
+impl TableColumn {
+    pub fn new(title: &str, cell: Component<ListRowProps>) -> Self {
+        TableColumn {
+            title: title.to_owned(),
+            sortable: false,
+            expand: false,
+            cell,
+        }
+    }
+
+    pub fn with_sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    pub fn with_expand(mut self, expand: bool) -> Self {
+        self.expand = expand;
+        self
+    }
+}
+
+impl PartialEq for TableColumn {
+    fn eq(&self, other: &Self) -> bool {
+        self.title == other.title
+            && self.sortable == other.sortable
+            && self.expand == other.expand
+            && self.cell as usize == other.cell as usize
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SelectionMode {
+    None,
+    Single,
+    Multiple,
+}
+
+impl SelectionMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SelectionMode::None => "none",
+            SelectionMode::Single => "single",
+            SelectionMode::Multiple => "multiple",
+        }
+    }
+}
+
+#[derive(Props)]
+pub struct TableProps<'a> {
+    layout: Option<Style>,
+    item_count: usize,
+    columns: Vec<TableColumn>,
+    #[props(default)]
+    items: ListItems,
+    /// Defaults to [`SelectionMode::Single`]
+    selection_mode: Option<SelectionMode>,
+    #[props(default)]
+    on_select: EventHandler<'a, SelectionEvent>,
+    #[props(default)]
+    on_sort_change: EventHandler<'a, SortChangeEvent>,
+}
+
+pub fn Table<'a>(cx: Scope<'a, TableProps<'a>>) -> Element {
+    let layout = use_value(&cx, cx.props.layout.as_ref());
+    let columns = use_value(&cx, Some(&cx.props.columns));
+    let items = use_value(&cx, Some(&cx.props.items));
+    cx.render(LazyNodes::new(move |f| {
+        let bump = &f.bump();
+        let mut attrs = BumpVec::new_in(bump);
+        attrs.push(f.attr(
+            "item_count",
+            format_args!("{}", cx.props.item_count),
+            None,
+            false,
+        ));
+        if let Some(columns) = columns {
+            attrs.push(f.attr("columns", format_args!("{}", columns), None, false));
+        }
+        if let Some(items) = items {
+            attrs.push(f.attr("items", format_args!("{}", items), None, false));
+        }
+        if let Some(mode) = cx.props.selection_mode {
+            attrs.push(f.attr(
+                "selection_mode",
+                format_args!("{}", mode.as_str()),
+                None,
+                false,
+            ));
+        }
+        if let Some(layout) = layout {
+            attrs.push(f.attr("layout", format_args!("{}", layout), None, false));
+        }
+        let mut listeners = BumpVec::new_in(bump);
+
+        use dioxus_core::AnyEvent;
+        // we can't allocate unsized in bumpalo's box, so we need to craft the box manually
+        // safety: this is essentially the same as calling Box::new() but manually
+        // The box is attached to the lifetime of the bumpalo allocator
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<SelectionData>().unwrap();
+            cx.props.on_select.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("select", handler));
+
+        let cb: &mut dyn FnMut(AnyEvent) = bump.alloc(move |evt: AnyEvent| {
+            let event = evt.downcast::<SortChangeData>().unwrap();
+            cx.props.on_sort_change.call(event);
+        });
+
+        let callback: BumpBox<dyn FnMut(AnyEvent) + 'a> = unsafe { BumpBox::from_raw(cb) };
+
+        let handler = bump.alloc(std::cell::RefCell::new(Some(callback)));
+        listeners.push(f.listener("sort_change", handler));
+        f.raw_element(
+            "gtk_table",
+            None,
+            listeners.into_bump_slice(),
+            attrs.into_bump_slice(),
+            &[],
+            None,
+        )
+    }))
+}
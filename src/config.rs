@@ -0,0 +1,61 @@
+use gtk::gio::ApplicationFlags;
+
+/// Options for [`launch_with_config`](crate::launch_with_config).
+pub struct LaunchConfig<P = ()> {
+    pub(crate) application_id: String,
+    pub(crate) flags: ApplicationFlags,
+    pub(crate) default_size: Option<(i32, i32)>,
+    pub(crate) props: P,
+    pub(crate) css: Option<String>,
+    pub(crate) hold: bool,
+}
+
+impl LaunchConfig<()> {
+    pub fn new(application_id: &str) -> Self {
+        LaunchConfig {
+            application_id: application_id.to_owned(),
+            flags: ApplicationFlags::empty(),
+            default_size: None,
+            props: (),
+            css: None,
+            hold: false,
+        }
+    }
+}
+
+impl<P> LaunchConfig<P> {
+    /// Props passed to the root component.
+    pub fn with_props<T>(self, props: T) -> LaunchConfig<T> {
+        LaunchConfig {
+            application_id: self.application_id,
+            flags: self.flags,
+            default_size: self.default_size,
+            props,
+            css: self.css,
+            hold: self.hold,
+        }
+    }
+
+    pub fn with_flags(mut self, flags: ApplicationFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Size the first window opens with before the user resizes it.
+    pub fn with_default_size(mut self, width: i32, height: i32) -> Self {
+        self.default_size = Some((width, height));
+        self
+    }
+
+    /// Style sheet loaded into a `gtk::CssProvider` for the whole display.
+    pub fn with_css(mut self, css: &str) -> Self {
+        self.css = Some(css.to_owned());
+        self
+    }
+
+    /// Keeps the application running while it has no open windows.
+    pub fn with_hold(mut self, hold: bool) -> Self {
+        self.hold = hold;
+        self
+    }
+}
@@ -9,3 +9,69 @@ pub struct TextChangeData {
 }
 
 pub type TextChangeEvent = UiEvent<TextChangeData>;
+
+pub struct ScrollData {
+    pub x: f64,
+    pub y: f64,
+    pub content_width: f64,
+    pub content_height: f64,
+    pub viewport_width: f64,
+    pub viewport_height: f64,
+}
+
+pub type ScrollEvent = UiEvent<ScrollData>;
+
+pub struct SelectionData {
+    /// Indices of the selected rows, in ascending order
+    pub rows: Vec<usize>,
+}
+
+pub type SelectionEvent = UiEvent<SelectionData>;
+
+pub struct SortChangeData {
+    /// Index of the column the rows should be sorted by, if any
+    pub column: Option<usize>,
+    pub ascending: bool,
+}
+
+pub type SortChangeEvent = UiEvent<SortChangeData>;
+
+pub struct ToggleData {
+    pub active: bool,
+}
+
+pub type ToggleEvent = UiEvent<ToggleData>;
+
+pub struct ValueChangeData {
+    pub value: f64,
+}
+
+pub type ValueChangeEvent = UiEvent<ValueChangeData>;
+
+pub struct SelectData {
+    /// Index of the selected option, if any
+    pub index: Option<usize>,
+    pub value: Option<String>,
+}
+
+pub type SelectEvent = UiEvent<SelectData>;
+
+pub struct CursorMoveData {
+    /// Offset of the cursor in characters from the start of the text
+    pub offset: usize,
+    pub line: usize,
+    /// Offset of the cursor in characters from the start of its line
+    pub column: usize,
+}
+
+pub type CursorMoveEvent = UiEvent<CursorMoveData>;
+
+pub struct PointerData {
+    /// Position relative to the top left corner of the component
+    pub x: f64,
+    pub y: f64,
+    /// Mouse button that was pressed or released, 0 for moves and touch
+    pub button: u32,
+}
+
+pub type PointerEvent = UiEvent<PointerData>;
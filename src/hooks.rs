@@ -0,0 +1,116 @@
+use dioxus_core::ScopeState;
+use gtk::gio::File;
+use std::{
+    cell::RefCell,
+    ffi::OsString,
+    rc::{Rc, Weak},
+};
+
+/// Signals of the underlying `gtk::Application` that components can listen to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppEvent {
+    /// Emitted once, right after the first render.
+    Startup,
+    /// Emitted whenever the application is activated, including by a second instance.
+    Activate,
+    /// Emitted before the application exits, while the component tree is still alive.
+    Shutdown,
+    /// Emitted when files are opened, `use_app_args` has the new list by then.
+    Open,
+    /// Emitted when a command line is received, requires `ApplicationFlags::HANDLES_COMMAND_LINE`.
+    CommandLine,
+}
+
+/// Arguments the application was started with, or last sent by another instance.
+#[derive(Clone, Debug)]
+pub struct AppArgs {
+    pub arguments: Vec<OsString>,
+    /// Files from the last `open` signal, requires `ApplicationFlags::HANDLES_OPEN`.
+    pub files: Vec<File>,
+    pub hint: String,
+}
+
+impl Default for AppArgs {
+    fn default() -> Self {
+        AppArgs {
+            arguments: std::env::args_os().collect(),
+            files: Vec::new(),
+            hint: String::new(),
+        }
+    }
+}
+
+type AppEventHandler = RefCell<Box<dyn FnMut()>>;
+
+/// Registry of [`use_app_event`] handlers, shared with the application signal handlers.
+#[derive(Clone, Default)]
+pub(crate) struct Lifecycle {
+    handlers: Rc<RefCell<Vec<(AppEvent, Weak<AppEventHandler>)>>>,
+    args: Rc<RefCell<AppArgs>>,
+    args_subscribers: Rc<RefCell<Vec<Weak<dyn Fn()>>>>,
+}
+
+impl Lifecycle {
+    pub(crate) fn emit(&self, event: AppEvent) {
+        // Handlers of unmounted components have been dropped along with their scope
+        self.handlers
+            .borrow_mut()
+            .retain(|(_, handler)| handler.strong_count() > 0);
+        let handlers = self
+            .handlers
+            .borrow()
+            .iter()
+            .filter(|(kind, _)| *kind == event)
+            .filter_map(|(_, handler)| handler.upgrade())
+            .collect::<Vec<_>>();
+        for handler in handlers {
+            (handler.borrow_mut())();
+        }
+    }
+
+    /// Updates the arguments and re-renders every component that reads them.
+    pub(crate) fn update_args(&self, update: impl FnOnce(&mut AppArgs)) {
+        update(&mut self.args.borrow_mut());
+        self.args_subscribers
+            .borrow_mut()
+            .retain(|subscriber| subscriber.strong_count() > 0);
+        for subscriber in self.args_subscribers.borrow().iter() {
+            if let Some(subscriber) = subscriber.upgrade() {
+                subscriber();
+            }
+        }
+    }
+}
+
+/// Runs `handler` whenever the application emits `event`.
+///
+/// The handler is replaced on every render so it always sees the latest state.
+pub fn use_app_event(cx: &ScopeState, event: AppEvent, handler: impl FnMut() + 'static) {
+    let slot = cx.use_hook(|_| {
+        let slot: Rc<AppEventHandler> = Rc::new(RefCell::new(Box::new(|| {})));
+        cx.consume_context::<Lifecycle>()
+            .expect("use_app_event must be used inside an app started with launch")
+            .handlers
+            .borrow_mut()
+            .push((event, Rc::downgrade(&slot)));
+        slot
+    });
+    *slot.borrow_mut() = Box::new(handler);
+}
+
+/// Returns the command line arguments and opened files, re-rendering when they change.
+pub fn use_app_args(cx: &ScopeState) -> AppArgs {
+    let (lifecycle, _subscriber) = cx.use_hook(|_| {
+        let lifecycle = cx
+            .consume_context::<Lifecycle>()
+            .expect("use_app_args must be used inside an app started with launch");
+        let update = cx.schedule_update();
+        let subscriber: Rc<dyn Fn()> = Rc::new(move || update());
+        lifecycle
+            .args_subscribers
+            .borrow_mut()
+            .push(Rc::downgrade(&subscriber));
+        (lifecycle, subscriber)
+    });
+    lifecycle.args.borrow().clone()
+}
@@ -0,0 +1,147 @@
+use gtk::{glib, prelude::*, subclass::prelude::*, Allocation, Orientation, Widget};
+use hashbrown::HashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+use taffy::prelude::*;
+
+/// The Taffy tree, shared between the renderer and the layout managers of its containers.
+#[derive(Default)]
+pub struct LayoutTree {
+    pub taffy: Taffy,
+    /// Widgets that a container allocates for its child nodes
+    pub widgets: HashMap<Node, Widget>,
+}
+
+mod imp {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Default)]
+    pub struct TaffyLayout {
+        pub(super) tree: RefCell<Option<Rc<RefCell<LayoutTree>>>>,
+        pub(super) node: Cell<Option<Node>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TaffyLayout {
+        const NAME: &'static str = "GtkPlatformTaffyLayout";
+        type Type = super::TaffyLayout;
+        type ParentType = gtk::LayoutManager;
+    }
+
+    impl ObjectImpl for TaffyLayout {}
+
+    impl LayoutManagerImpl for TaffyLayout {
+        fn request_mode(
+            &self,
+            _layout_manager: &Self::Type,
+            _widget: &Widget,
+        ) -> gtk::SizeRequestMode {
+            gtk::SizeRequestMode::ConstantSize
+        }
+
+        fn measure(
+            &self,
+            _layout_manager: &Self::Type,
+            widget: &Widget,
+            orientation: Orientation,
+            _for_size: i32,
+        ) -> (i32, i32, i32, i32) {
+            let tree = self.tree.borrow();
+            let mut tree = tree.as_ref().unwrap().borrow_mut();
+            let node = self.node.get().unwrap();
+            if !is_nested(widget) {
+                tree.taffy
+                    .compute_layout(
+                        node,
+                        Size {
+                            width: Number::Undefined,
+                            height: Number::Undefined,
+                        },
+                    )
+                    .unwrap();
+            }
+            // A nested container is sized by its parent's layout, a root one by its content.
+            // Taffy has no min-content sizing, so the natural size is the minimum as well, as
+            // anything smaller would allocate children outside the container.
+            let size = tree.taffy.layout(node).unwrap().size;
+            let size = match orientation {
+                Orientation::Horizontal => size.width,
+                _ => size.height,
+            }
+            .ceil() as i32;
+            (size, size, -1, -1)
+        }
+
+        fn allocate(
+            &self,
+            _layout_manager: &Self::Type,
+            widget: &Widget,
+            width: i32,
+            height: i32,
+            _baseline: i32,
+        ) {
+            let allocations = {
+                let tree = self.tree.borrow();
+                let mut tree = tree.as_ref().unwrap().borrow_mut();
+                let node = self.node.get().unwrap();
+                if !is_nested(widget) {
+                    tree.taffy
+                        .compute_layout(
+                            node,
+                            Size {
+                                width: Number::Defined(width as f32),
+                                height: Number::Defined(height as f32),
+                            },
+                        )
+                        .unwrap();
+                }
+                tree.taffy
+                    .children(node)
+                    .unwrap()
+                    .into_iter()
+                    .filter_map(|child| {
+                        let layout = tree.taffy.layout(child).unwrap();
+                        let allocation = Allocation::new(
+                            layout.location.x.round() as i32,
+                            layout.location.y.round() as i32,
+                            layout.size.width.round() as i32,
+                            layout.size.height.round() as i32,
+                        );
+                        Some((tree.widgets.get(&child)?.clone(), allocation))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            // Nested containers lay out their own children, so the tree must not be borrowed
+            for (child, allocation) in allocations {
+                // GTK expects every child to be measured before it is allocated
+                child.measure(Orientation::Horizontal, -1);
+                child.size_allocate(&allocation, -1);
+            }
+        }
+    }
+
+    /// Containers inside another Taffy container already have a computed layout, anything
+    /// else (a window, a scrolled window) is the root of its own Taffy layout.
+    fn is_nested(widget: &Widget) -> bool {
+        widget
+            .parent()
+            .and_then(|parent| parent.layout_manager())
+            .map_or(false, |manager| manager.is::<super::TaffyLayout>())
+    }
+}
+
+glib::wrapper! {
+    /// Lays out the children of a container from the container's Taffy node during GTK's own
+    /// measure and allocate cycle.
+    pub struct TaffyLayout(ObjectSubclass<imp::TaffyLayout>) @extends gtk::LayoutManager;
+}
+
+impl TaffyLayout {
+    pub fn new(tree: Rc<RefCell<LayoutTree>>, node: Node) -> Self {
+        let layout: Self = glib::Object::new(&[]).expect("Failed to create TaffyLayout");
+        layout.imp().tree.replace(Some(tree));
+        layout.imp().node.set(Some(node));
+        layout
+    }
+}
@@ -1,71 +1,161 @@
 use dioxus_core::{prelude::*, SchedulerMsg, UserEvent};
+use futures::channel::mpsc::UnboundedReceiver;
 use futures::{select, FutureExt, StreamExt};
+use gtk::gdk::Display;
 use gtk::glib::{clone, MainContext};
 use gtk::prelude::*;
-use gtk::Application;
-use hashbrown::HashMap;
-use renderer::{NativeWidget, Renderer, Widgets};
-use snafu::Whatever;
+use gtk::{Application, CssProvider, StyleContext};
+use hooks::{AppEvent, Lifecycle};
+use layout_manager::LayoutTree;
+use renderer::Renderer;
+use slotmap::DefaultKey;
+use snafu::{ResultExt, Whatever};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
 pub mod components;
+mod config;
 pub mod events;
+pub mod hooks;
+mod layout_manager;
+mod list;
 mod renderer;
+mod values;
+pub use config::LaunchConfig;
+pub use gtk::cairo;
 pub use taffy::*;
 
 enum MainEvent {
-    Resize,
+    InvalidateLayout(Option<DefaultKey>),
     Render,
+    /// Renders the root component again, for trees whose props are read from shared state
+    Rerender,
     UserEvent(UserEvent),
+    /// Frees the tree and stops driving it
+    Close,
+}
+
+/// Drives `dom` until it is closed, applying its edits with `renderer`.
+async fn run(
+    mut dom: VirtualDom,
+    mut renderer: Renderer,
+    mut receiver: UnboundedReceiver<MainEvent>,
+) {
+    let root = dom.base_scope().scope_id();
+    loop {
+        match select!(
+            evt = receiver.next() => evt.unwrap(),
+            _ = dom.wait_for_work().fuse() => MainEvent::Render,
+        ) {
+            MainEvent::InvalidateLayout(key) => {
+                renderer.invalidate_layout(key);
+            }
+            MainEvent::Render => {
+                for edits in dom.work_with_deadline(|| false) {
+                    renderer.apply(edits);
+                }
+                renderer.queue_layout();
+            }
+            MainEvent::Rerender => {
+                dom.handle_message(SchedulerMsg::Immediate(root));
+            }
+            MainEvent::UserEvent(evt) => {
+                dom.handle_message(SchedulerMsg::Event(evt));
+            }
+            MainEvent::Close => {
+                renderer.close();
+                break;
+            }
+        }
+    }
 }
 
 pub fn launch(c: Component, application_id: &str) -> Result<(), Whatever> {
+    launch_with_config(c, LaunchConfig::new(application_id))
+}
+
+pub fn launch_with_config<P: 'static>(
+    c: Component<P>,
+    config: LaunchConfig<P>,
+) -> Result<(), Whatever> {
+    // Components are polled on the glib main loop, but tokio timers and IO need a reactor.
+    // Entering the runtime lets them register with it while its workers drive the reactor.
+    let runtime =
+        tokio::runtime::Runtime::new().whatever_context("Failed to start the tokio runtime")?;
+    let _runtime_guard = runtime.enter();
     let app = Application::builder()
-        .application_id(application_id)
+        .application_id(&config.application_id)
+        .flags(config.flags)
         .build();
-    app.connect_activate(move |app: &Application| {
-        let (sender, mut receiver) = futures::channel::mpsc::unbounded::<MainEvent>();
-        let mut renderer = Renderer {
-            widgets: Widgets::default(),
-            taffy: Taffy::new(),
-            roots: HashMap::new(),
-            taffy_nodes: HashMap::new(),
-            app: app.clone(),
-            sender: sender.clone(),
-        };
-        let mut dom = VirtualDom::new(c);
-        let mutations = dom.rebuild();
-        renderer.apply(mutations);
-        if let NativeWidget::Window(window) = &renderer.widgets.gtk[renderer.roots[&1]] {
-            window.connect_default_height_notify(clone!(@strong sender => move |_window| {
-                sender.unbounded_send(MainEvent::Resize).unwrap();
-            }));
-            window.connect_default_width_notify(clone!(@strong sender => move |_window| {
-                sender.unbounded_send(MainEvent::Resize).unwrap();
-            }));
-        }
-        let main_context = MainContext::default();
-        main_context.spawn_local(clone!(@strong app => async move {
-            loop {
-                match select!(
-                    evt = receiver.next() => evt.unwrap(),
-                    _ = dom.wait_for_work().fuse() => MainEvent::Render,
-                ) {
-                    MainEvent::Resize => {
-                        renderer.recalculate_layout();
-                    },
-                    MainEvent::Render => {
-                        for edits in dom.work_with_deadline(|| false) {
-                            renderer.apply(edits);
-                        }
-                        renderer.recalculate_layout();
-                    },
-                    MainEvent::UserEvent(evt) => {
-                        dom.handle_message(SchedulerMsg::Event(evt));
-                    },
-                }
+    let LaunchConfig {
+        default_size,
+        props,
+        css,
+        hold,
+        ..
+    } = config;
+    // startup is only emitted once, but the handler has to be Fn
+    let props = Cell::new(Some(props));
+    let lifecycle = Lifecycle::default();
+    // Releases the hold when dropped, which happens on shutdown
+    let hold_guard = Rc::new(RefCell::new(None));
+    // The tree is built on startup rather than activate, which is emitted again every time
+    // another instance of the application is launched
+    app.connect_startup(
+        clone!(@strong lifecycle, @strong hold_guard => move |app: &Application| {
+            if let Some(ref css) = css {
+                let provider = CssProvider::new();
+                provider.load_from_data(css.as_bytes());
+                StyleContext::add_provider_for_display(
+                    &Display::default().expect("Could not connect to a display"),
+                    &provider,
+                    gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+                );
+            }
+            if hold {
+                *hold_guard.borrow_mut() = Some(app.hold());
             }
-        }));
-    });
+            let (sender, receiver) = futures::channel::mpsc::unbounded::<MainEvent>();
+            let mut renderer = Renderer::new(
+                app.clone(),
+                Rc::new(RefCell::new(LayoutTree::default())),
+                lifecycle.clone(),
+                default_size,
+                sender,
+            );
+            renderer.track_settings();
+            let mut dom = VirtualDom::new_with_props(c, props.take().unwrap());
+            dom.base_scope().provide_context(lifecycle.clone());
+            let mutations = dom.rebuild();
+            renderer.apply(mutations);
+            MainContext::default().spawn_local(run(dom, renderer, receiver));
+            lifecycle.emit(AppEvent::Startup);
+        }),
+    );
+    app.connect_activate(clone!(@strong lifecycle => move |app| {
+        // Another instance was launched, which should bring the open windows to the front
+        for window in app.windows() {
+            window.present();
+        }
+        lifecycle.emit(AppEvent::Activate);
+    }));
+    app.connect_command_line(clone!(@strong lifecycle => move |app, command_line| {
+        lifecycle.update_args(|args| args.arguments = command_line.arguments());
+        lifecycle.emit(AppEvent::CommandLine);
+        app.activate();
+        0
+    }));
+    app.connect_open(clone!(@strong lifecycle => move |_, files, hint| {
+        lifecycle.update_args(|args| {
+            args.files = files.to_vec();
+            args.hint = hint.to_owned();
+        });
+        lifecycle.emit(AppEvent::Open);
+    }));
+    app.connect_shutdown(clone!(@strong lifecycle => move |_| {
+        hold_guard.borrow_mut().take();
+        lifecycle.emit(AppEvent::Shutdown);
+    }));
     app.run();
     Ok(())
 }
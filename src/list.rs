@@ -0,0 +1,297 @@
+use crate::components::{ListItems, ListRowProps, TableColumn};
+use crate::hooks::Lifecycle;
+use crate::layout_manager::LayoutTree;
+use crate::renderer::Renderer;
+use crate::{run, MainEvent};
+use dioxus_core::prelude::*;
+use futures::channel::mpsc::UnboundedSender;
+use gtk::gio::ListStore;
+use gtk::glib::{clone, MainContext, Object};
+use gtk::{
+    prelude::*, Application, ColumnView, ColumnViewColumn, CustomSorter, ListItem, ListView,
+    MultiSelection, NoSelection, SignalListItemFactory, SingleSelection,
+};
+use hashbrown::HashMap;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Renders the rows of a list, shared through the value table.
+#[derive(Clone)]
+pub(crate) struct ListSource {
+    pub row: Component<ListRowProps>,
+    pub items: ListItems,
+}
+
+impl PartialEq for ListSource {
+    fn eq(&self, other: &Self) -> bool {
+        self.row as usize == other.row as usize && self.items == other.items
+    }
+}
+
+/// Index of the bound row and the channel of the tree rendering it
+type Row = (Rc<Cell<Option<usize>>>, UnboundedSender<MainEvent>);
+
+/// Renders each list item with a small tree of its own. Trees are only created for the items
+/// GTK needs to show, and are recycled as they scroll out of sight.
+pub struct RowFactory {
+    pub factory: SignalListItemFactory,
+    source: Rc<RefCell<Option<ListSource>>>,
+    rows: Rc<RefCell<HashMap<ListItem, Row>>>,
+}
+
+impl RowFactory {
+    pub fn new(app: &Application, layout: &Rc<RefCell<LayoutTree>>, lifecycle: &Lifecycle) -> Self {
+        let source = Rc::new(RefCell::new(None));
+        let rows: Rc<RefCell<HashMap<ListItem, Row>>> = Default::default();
+        let factory = SignalListItemFactory::new();
+        factory.connect_setup(
+            clone!(@strong app, @strong layout, @strong lifecycle, @strong source, @strong rows =>
+            move |_, item| {
+                let (sender, receiver) = futures::channel::mpsc::unbounded::<MainEvent>();
+                let mut renderer = Renderer::new(
+                    app.clone(),
+                    layout.clone(),
+                    lifecycle.clone(),
+                    None,
+                    sender.clone(),
+                );
+                item.set_child(Some(&renderer.host()));
+                // Rendered once bound to a row
+                let index = Rc::new(Cell::new(None));
+                let mut dom = VirtualDom::new_with_props(
+                    RowRoot,
+                    RowRootProps {
+                        index: index.clone(),
+                        source: source.clone(),
+                    },
+                );
+                // Rows can use the application hooks like the rest of the app
+                dom.base_scope().provide_context(lifecycle.clone());
+                renderer.apply(dom.rebuild());
+                MainContext::default().spawn_local(run(dom, renderer, receiver));
+                rows.borrow_mut().insert(item.clone(), (index, sender));
+            }),
+        );
+        factory.connect_bind(clone!(@strong rows => move |_, list_item| {
+            if let Some((index, sender)) = rows.borrow().get(list_item) {
+                index.set(Some(list_item.position() as usize));
+                sender.unbounded_send(MainEvent::Rerender).unwrap();
+            }
+        }));
+        factory.connect_teardown(clone!(@strong rows => move |_, list_item| {
+            if let Some((_, sender)) = rows.borrow_mut().remove(list_item) {
+                list_item.set_child(None::<&gtk::Widget>);
+                sender.unbounded_send(MainEvent::Close).unwrap();
+            }
+        }));
+        RowFactory {
+            factory,
+            source,
+            rows,
+        }
+    }
+
+    /// Renders the bound rows again with the new source.
+    pub fn set_source(&self, source: Option<ListSource>) {
+        *self.source.borrow_mut() = source;
+        for (_, sender) in self.rows.borrow().values() {
+            sender.unbounded_send(MainEvent::Rerender).unwrap();
+        }
+    }
+}
+
+/// Grows or shrinks `store` at its end, so rows that are already bound stay in place. Rows
+/// are rendered by index, so its items only need to exist.
+fn set_len(store: &ListStore, len: u32) {
+    let current = store.n_items();
+    if len > current {
+        let additions = (current..len)
+            .map(|_| Object::new::<Object>(&[]).unwrap())
+            .collect::<Vec<_>>();
+        store.splice(current, 0, &additions);
+    } else if len < current {
+        store.splice(len, current - len, &[] as &[Object]);
+    }
+}
+
+pub struct ListState {
+    pub view: ListView,
+    store: ListStore,
+    rows: RowFactory,
+}
+
+impl ListState {
+    pub fn new(app: &Application, layout: &Rc<RefCell<LayoutTree>>, lifecycle: &Lifecycle) -> Self {
+        let store = ListStore::new(Object::static_type());
+        let rows = RowFactory::new(app, layout, lifecycle);
+        let view = ListView::new(Some(&NoSelection::new(Some(&store))), Some(&rows.factory));
+        ListState { view, store, rows }
+    }
+
+    pub fn set_len(&self, len: u32) {
+        set_len(&self.store, len);
+    }
+
+    pub fn set_source(&self, source: Option<ListSource>) {
+        self.rows.set_source(source);
+    }
+}
+
+/// Finds the sort column of a column view by comparing two items with its sorter, as GTK
+/// does not expose it otherwise. Each column's sorter records itself and orders the first
+/// item first, which the column view inverts for descending columns.
+pub struct SortProbe {
+    column: Cell<Option<usize>>,
+    items: (Object, Object),
+}
+
+impl SortProbe {
+    /// Returns the primary sort column and whether it is ascending.
+    pub fn read(&self, view: &ColumnView) -> Option<(usize, bool)> {
+        self.column.set(None);
+        let ordering = view.sorter()?.compare(&self.items.0, &self.items.1);
+        self.column
+            .get()
+            .map(|column| (column, ordering != gtk::Ordering::Larger))
+    }
+}
+
+pub struct TableState {
+    pub view: ColumnView,
+    pub single: SingleSelection,
+    pub multi: MultiSelection,
+    pub sort: Rc<SortProbe>,
+    none: NoSelection,
+    store: ListStore,
+    columns: Vec<(TableColumn, ColumnViewColumn, RowFactory)>,
+    items: ListItems,
+    app: Application,
+    layout: Rc<RefCell<LayoutTree>>,
+    lifecycle: Lifecycle,
+}
+
+impl TableState {
+    pub fn new(app: &Application, layout: &Rc<RefCell<LayoutTree>>, lifecycle: &Lifecycle) -> Self {
+        let store = ListStore::new(Object::static_type());
+        // Every mode has its own model, so selection handlers survive switching between them
+        let single = SingleSelection::new(Some(&store));
+        single.set_autoselect(false);
+        single.set_can_unselect(true);
+        let multi = MultiSelection::new(Some(&store));
+        let none = NoSelection::new(Some(&store));
+        let view = ColumnView::new(Some(&single));
+        TableState {
+            view,
+            single,
+            multi,
+            sort: Rc::new(SortProbe {
+                column: Cell::new(None),
+                items: (
+                    Object::new::<Object>(&[]).unwrap(),
+                    Object::new::<Object>(&[]).unwrap(),
+                ),
+            }),
+            none,
+            store,
+            columns: Vec::new(),
+            items: ListItems::default(),
+            app: app.clone(),
+            layout: layout.clone(),
+            lifecycle: lifecycle.clone(),
+        }
+    }
+
+    pub fn set_len(&self, len: u32) {
+        set_len(&self.store, len);
+    }
+
+    pub fn set_selection_mode(&self, mode: &str) {
+        match mode {
+            "none" => self.view.set_model(Some(&self.none)),
+            "multiple" => self.view.set_model(Some(&self.multi)),
+            _ => self.view.set_model(Some(&self.single)),
+        }
+    }
+
+    pub fn set_items(&mut self, items: ListItems) {
+        self.items = items;
+        for (definition, _, rows) in &self.columns {
+            rows.set_source(Some(ListSource {
+                row: definition.cell,
+                items: self.items.clone(),
+            }));
+        }
+    }
+
+    /// Updates columns in place, so the trees of their cells are kept.
+    pub fn set_columns(&mut self, definitions: Vec<TableColumn>) {
+        for (_, column, _) in self
+            .columns
+            .drain(definitions.len().min(self.columns.len())..)
+        {
+            self.view.remove_column(&column);
+        }
+        for (index, definition) in definitions.into_iter().enumerate() {
+            let source = ListSource {
+                row: definition.cell,
+                items: self.items.clone(),
+            };
+            match self.columns.get_mut(index) {
+                Some((current, column, rows)) => {
+                    if *current == definition {
+                        continue;
+                    }
+                    column.set_title(Some(&definition.title));
+                    column.set_expand(definition.expand);
+                    if current.sortable != definition.sortable {
+                        column.set_sorter(sorter(&self.sort, index, definition.sortable).as_ref());
+                    }
+                    if current.cell as usize != definition.cell as usize {
+                        rows.set_source(Some(source));
+                    }
+                    *current = definition;
+                }
+                None => {
+                    let rows = RowFactory::new(&self.app, &self.layout, &self.lifecycle);
+                    rows.set_source(Some(source));
+                    let column =
+                        ColumnViewColumn::new(Some(&definition.title), Some(&rows.factory));
+                    column.set_expand(definition.expand);
+                    column.set_resizable(true);
+                    column.set_sorter(sorter(&self.sort, index, definition.sortable).as_ref());
+                    self.view.append_column(&column);
+                    self.columns.push((definition, column, rows));
+                }
+            }
+        }
+    }
+}
+
+/// Rows are sorted by the application, the sorter only makes the column header clickable.
+fn sorter(probe: &Rc<SortProbe>, index: usize, sortable: bool) -> Option<CustomSorter> {
+    sortable.then(|| {
+        let probe = Rc::downgrade(probe);
+        CustomSorter::new(move |_, _| {
+            if let Some(probe) = probe.upgrade() {
+                probe.column.set(Some(index));
+            }
+            gtk::Ordering::Smaller
+        })
+    })
+}
+
+struct RowRootProps {
+    index: Rc<Cell<Option<usize>>>,
+    source: Rc<RefCell<Option<ListSource>>>,
+}
+
+/// Root of a row's tree. Its props are shared with the list, so it reads the current row
+/// whenever it is rendered again.
+#[allow(non_snake_case)]
+fn RowRoot(cx: Scope<RowRootProps>) -> Element {
+    let ListSource { row, items } = cx.props.source.borrow().clone()?;
+    let index = cx.props.index.get()?;
+    cx.render(LazyNodes::new(move |f| {
+        f.component(row, ListRowProps { index, items }, None, "ListRow")
+    }))
+}
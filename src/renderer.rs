@@ -1,26 +1,70 @@
-use crate::{events, MainEvent};
+use crate::components::{CloseRequest, CloseRequestFn, Draw, ImageSource};
+use crate::hooks::Lifecycle;
+use crate::layout_manager::{LayoutTree, TaffyLayout};
+use crate::list::{ListState, TableState};
+use crate::{events, values, MainEvent};
 use dioxus_core::{ElementId, EventPriority, Mutations, UserEvent};
 use futures::channel::mpsc::UnboundedSender;
-use gtk::{prelude::*, Application, ApplicationWindow, Fixed, Label, Widget};
+use gtk::gdk;
+use gtk::glib::{Object, SignalHandlerId};
+use gtk::{
+    prelude::*, Adjustment, Application, ApplicationWindow, Inhibit, Label, Orientation,
+    PolicyType, Settings, StringList, Widget, WrapMode,
+};
 use hashbrown::HashMap;
 use slotmap::{DefaultKey, SecondaryMap, SlotMap};
+use std::cell::{Cell, RefCell, RefMut};
+use std::rc::Rc;
 use std::sync::Arc;
+use taffy::number::OrElse;
 use taffy::prelude::*;
 
+/// Signal handlers of an event, along with the object each is connected to
+pub type EventHandlers = Vec<(Object, SignalHandlerId)>;
+
 #[derive(Default)]
 pub struct Widgets {
     pub main: SlotMap<DefaultKey, ()>,
     pub gtk: SecondaryMap<DefaultKey, NativeWidget>,
-    pub layout_root: SecondaryMap<DefaultKey, Fixed>,
+    pub layout_root: SecondaryMap<DefaultKey, gtk::Box>,
+    /// Taffy node of the layout root, when it is not the element's own node
+    pub layout_root_taffy: SecondaryMap<DefaultKey, Node>,
     pub layout_parent: SecondaryMap<DefaultKey, DefaultKey>,
+    pub children: SecondaryMap<DefaultKey, Vec<DefaultKey>>,
+    pub ids: SecondaryMap<DefaultKey, u64>,
+    pub handlers: SecondaryMap<DefaultKey, HashMap<&'static str, EventHandlers>>,
+    /// Close handler of a window, shared with its `close-request` signal handler
+    pub close_request: SecondaryMap<DefaultKey, Rc<RefCell<Option<CloseRequestFn>>>>,
+    pub lists: SecondaryMap<DefaultKey, ListState>,
+    pub tables: SecondaryMap<DefaultKey, TableState>,
+    /// Horizontal and vertical offsets a scroll view could not reach yet
+    pub scroll_to: SecondaryMap<DefaultKey, [Rc<Cell<Option<f64>>>; 2]>,
+    /// Radio group of check boxes
+    pub groups: SecondaryMap<DefaultKey, String>,
+    pub clicks: SecondaryMap<DefaultKey, gtk::GestureClick>,
+    /// Added to a canvas only while its pointer moves are listened to
+    pub motion: SecondaryMap<DefaultKey, gtk::EventControllerMotion>,
+    /// Option a dropdown's props select, restored when its options change
+    pub selected: SecondaryMap<DefaultKey, u32>,
+    /// Last pulse count of a progress bar
+    pub pulses: SecondaryMap<DefaultKey, u64>,
+    /// Names of the offsets added to a level bar
+    pub offsets: SecondaryMap<DefaultKey, Vec<String>>,
     pub taffy: SecondaryMap<DefaultKey, Node>,
 }
 
 pub struct Renderer {
     pub widgets: Widgets,
     pub roots: HashMap<u64, DefaultKey>,
-    pub taffy_nodes: HashMap<Node, DefaultKey>,
-    pub taffy: Taffy,
+    pub layout: Rc<RefCell<LayoutTree>>,
+    /// Passed on to the trees of list rows
+    pub(crate) lifecycle: Lifecycle,
+    /// Top-level windows (and placeholders for conditional ones) in render order
+    pub top_level: Vec<DefaultKey>,
+    /// Default size of the first window, taken once it is created
+    pub default_size: Option<(i32, i32)>,
+    /// Container the top-level components are placed in, for trees embedded in a widget
+    pub host: Option<DefaultKey>,
     pub app: Application,
     pub(crate) sender: UnboundedSender<MainEvent>,
 }
@@ -31,6 +75,21 @@ pub enum NativeWidget {
     Window(ApplicationWindow),
     Button(gtk::Button),
     TextField(gtk::Entry),
+    ScrollView(gtk::ScrolledWindow),
+    List(gtk::ScrolledWindow),
+    Table(gtk::ScrolledWindow),
+    CheckBox(gtk::CheckButton),
+    Switch(gtk::Switch),
+    ToggleButton(gtk::ToggleButton),
+    Slider(gtk::Scale),
+    SpinButton(gtk::SpinButton),
+    Dropdown(gtk::DropDown),
+    TextArea(gtk::TextView),
+    Image(gtk::Picture),
+    Canvas(gtk::DrawingArea),
+    ProgressBar(gtk::ProgressBar),
+    Spinner(gtk::Spinner),
+    LevelBar(gtk::LevelBar),
 }
 
 impl NativeWidget {
@@ -41,41 +100,257 @@ impl NativeWidget {
             NativeWidget::Window(widget) => widget.clone().upcast::<Widget>(),
             NativeWidget::Button(widget) => widget.clone().upcast::<Widget>(),
             NativeWidget::TextField(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::ScrollView(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::List(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::Table(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::CheckBox(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::Switch(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::ToggleButton(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::Slider(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::SpinButton(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::Dropdown(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::TextArea(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::Image(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::Canvas(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::ProgressBar(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::Spinner(widget) => widget.clone().upcast::<Widget>(),
+            NativeWidget::LevelBar(widget) => widget.clone().upcast::<Widget>(),
+        }
+    }
+
+    fn adjustment(&self) -> Option<Adjustment> {
+        match self {
+            NativeWidget::Slider(widget) => Some(widget.adjustment()),
+            NativeWidget::SpinButton(widget) => Some(widget.adjustment()),
+            _ => None,
+        }
+    }
+}
+
+/// Measures `widget` by its preferred size rather than its allocation, which is empty before
+/// the first frame. Dimensions Taffy already knows are kept, so a label wraps to a known width.
+fn measure_widget(widget: &impl IsA<Widget>, known: Size<Number>) -> Size<f32> {
+    let (min_width, natural_width, _, _) = widget.measure(Orientation::Horizontal, -1);
+    let width = match known.width {
+        Number::Defined(width) => width,
+        Number::Undefined => natural_width as f32,
+    };
+    let height = match known.height {
+        Number::Defined(height) => height,
+        Number::Undefined => {
+            // GTK rejects measuring for a width below the minimum
+            let for_width = (width as i32).max(min_width);
+            widget.measure(Orientation::Vertical, for_width).1 as f32
+        }
+    };
+    Size { width, height }
+}
+
+/// Numeric inputs start out with the same range, until their props are applied.
+fn default_adjustment() -> Adjustment {
+    Adjustment::new(0., 0., 100., 1., 10., 0.)
+}
+
+fn set_adjustment(adjustment: &Adjustment, field: &str, value: f64) {
+    match field {
+        "min" => adjustment.set_lower(value),
+        "max" => adjustment.set_upper(value),
+        "step" => {
+            adjustment.set_step_increment(value);
+            adjustment.set_page_increment(value * 10.);
+        }
+        _ => {
+            if adjustment.value() != value {
+                adjustment.set_value(value);
+            }
         }
     }
 }
 
+/// Returns what to replace to turn `current` into `options`: the position, the number of
+/// options removed there and the options inserted in their place. Only the options between
+/// the common prefix and suffix are replaced.
+fn options_splice<'a>(
+    current: &[impl AsRef<str>],
+    options: &'a [String],
+) -> (usize, usize, &'a [String]) {
+    let prefix = current
+        .iter()
+        .zip(options)
+        .take_while(|(current, option)| current.as_ref() == option.as_str())
+        .count();
+    let suffix = current[prefix..]
+        .iter()
+        .rev()
+        .zip(options[prefix..].iter().rev())
+        .take_while(|(current, option)| current.as_ref() == option.as_str())
+        .count();
+    (
+        prefix,
+        current.len() - prefix - suffix,
+        &options[prefix..options.len() - suffix],
+    )
+}
+
+/// Replaces only the options that changed, so the selected option stays selected unless it
+/// was replaced.
+fn update_options(list: &StringList, options: &[String]) {
+    let current = (0..list.n_items())
+        .filter_map(|index| list.string(index))
+        .collect::<Vec<_>>();
+    let (position, removed, added) = options_splice(&current, options);
+    if removed > 0 || !added.is_empty() {
+        let added = added.iter().map(String::as_str).collect::<Vec<_>>();
+        list.splice(position as u32, removed as u32, &added);
+    }
+}
+
+fn wrap_mode(value: &str) -> WrapMode {
+    match value {
+        "char" => WrapMode::Char,
+        "word" => WrapMode::Word,
+        "word_char" => WrapMode::WordChar,
+        _ => WrapMode::None,
+    }
+}
+
+/// Pictures are measured by the intrinsic size of what they show, rather than their preferred
+/// size, which ignores the aspect ratio. A known dimension scales the other one to match.
+fn measure_picture(picture: &gtk::Picture, known: Size<Number>) -> Size<f32> {
+    let (width, height, aspect_ratio) = match picture.paintable() {
+        Some(paintable) => (
+            paintable.intrinsic_width() as f32,
+            paintable.intrinsic_height() as f32,
+            paintable.intrinsic_aspect_ratio() as f32,
+        ),
+        None => (0., 0., 0.),
+    };
+    match (known.width, known.height) {
+        (Number::Defined(width), Number::Undefined) if aspect_ratio > 0. => Size {
+            width,
+            height: width / aspect_ratio,
+        },
+        (Number::Undefined, Number::Defined(height)) if aspect_ratio > 0. => Size {
+            width: height * aspect_ratio,
+            height,
+        },
+        (known_width, known_height) => Size {
+            width: known_width.or_else(width),
+            height: known_height.or_else(height),
+        },
+    }
+}
+
+fn set_image_source(picture: &gtk::Picture, source: &ImageSource) {
+    match source {
+        ImageSource::File(path) => picture.set_file(Some(&gtk::gio::File::for_path(path))),
+        ImageSource::Resource(path) => picture.set_resource(Some(path.as_str())),
+        ImageSource::Icon { name, size } => {
+            let icon = gtk::IconTheme::for_display(&picture.display()).lookup_icon(
+                name,
+                &[],
+                *size,
+                picture.scale_factor(),
+                gtk::TextDirection::None,
+                gtk::IconLookupFlags::empty(),
+            );
+            picture.set_paintable(Some(&icon));
+        }
+        ImageSource::Bytes(bytes) => {
+            // Data that can't be decoded shows nothing, like a missing file does
+            let stream =
+                gtk::gio::MemoryInputStream::from_bytes(&gtk::glib::Bytes::from(&bytes[..]));
+            let texture =
+                gtk::gdk_pixbuf::Pixbuf::from_stream(&stream, None::<&gtk::gio::Cancellable>)
+                    .ok()
+                    .map(|pixbuf| gdk::Texture::for_pixbuf(&pixbuf));
+            picture.set_paintable(texture.as_ref());
+        }
+    }
+}
+
+/// Scrolls `adjustment` to `offset`, keeping it in `pending` while the content is too small to
+/// reach it.
+fn scroll_to(adjustment: &Adjustment, pending: &Cell<Option<f64>>, offset: Option<f64>) {
+    match offset {
+        Some(offset) => {
+            // Skipping the current offset keeps user scrolling from bouncing back
+            if adjustment.value() != offset {
+                adjustment.set_value(offset);
+            }
+            pending.set(Some(offset).filter(|offset| adjustment.value() != *offset));
+        }
+        None => pending.set(None),
+    }
+}
+
+fn scroll_policy(value: &str) -> PolicyType {
+    match value {
+        "always" => PolicyType::Always,
+        "never" => PolicyType::Never,
+        "external" => PolicyType::External,
+        _ => PolicyType::Automatic,
+    }
+}
+
+/// Windows fill their whole client area unless a layout is given.
+fn window_style() -> Style {
+    Style {
+        size: Size {
+            width: Dimension::Percent(1.),
+            height: Dimension::Percent(1.),
+        },
+        ..Default::default()
+    }
+}
+
 impl Renderer {
+    pub(crate) fn new(
+        app: Application,
+        layout: Rc<RefCell<LayoutTree>>,
+        lifecycle: Lifecycle,
+        default_size: Option<(i32, i32)>,
+        sender: UnboundedSender<MainEvent>,
+    ) -> Self {
+        Renderer {
+            widgets: Widgets::default(),
+            roots: HashMap::new(),
+            layout,
+            lifecycle,
+            top_level: Vec::new(),
+            default_size,
+            host: None,
+            app,
+            sender,
+        }
+    }
+
     pub fn apply(&mut self, mutations: Mutations) {
         let mut stack = Vec::new();
-        use taffy::node::MeasureFunc::*;
         for edit in mutations.edits {
             match edit {
                 dioxus_core::DomEdit::PushRoot { root } => stack.push(root),
                 dioxus_core::DomEdit::AppendChildren { many } if (many as usize) < stack.len() => {
                     let target_root = stack[stack.len() - many as usize - 1];
                     let target_key = self.roots[&target_root];
-                    let target_taffy = self.widgets.taffy[target_key];
-                    let layout_root = self.widgets.layout_root.get(target_key).expect(
-                        "Trying to add a child to a component which does not have a layout root",
-                    );
-                    for child_root in stack.drain(stack.len() - many as usize..) {
-                        let child_key = self.roots[&child_root];
-                        self.widgets.layout_parent.insert(child_key, target_key);
-                        if let Some(child_widget) = self.widgets.gtk.get(child_key) {
-                            layout_root.put(&child_widget.upcast(), 0., 0.);
-                        }
-                        if let Some(child_taffy) = self.widgets.taffy.get(child_key) {
-                            self.taffy.add_child(target_taffy, *child_taffy).unwrap();
-                        }
-                    }
+                    let children = stack
+                        .drain(stack.len() - many as usize..)
+                        .map(|child_root| self.roots[&child_root])
+                        .collect::<Vec<_>>();
+                    let index = self.widgets.children.get(target_key).map_or(0, Vec::len);
+                    self.insert_children(target_key, index, &children);
                 }
-                dioxus_core::DomEdit::AppendChildren { many } if many == 1 && stack.len() == 1 => {
-                    let target_key = self.roots[&stack.pop().unwrap()];
-                    let target_widget = &self.widgets.gtk[target_key];
-                    match target_widget {
-                        NativeWidget::Window(widget) => widget.present(),
-                        _ => unreachable!("Only Window can be a top-level component"),
+                dioxus_core::DomEdit::AppendChildren { many } if many as usize == stack.len() => {
+                    let children = stack
+                        .drain(..)
+                        .map(|child_root| self.roots[&child_root])
+                        .collect::<Vec<_>>();
+                    match self.host {
+                        Some(host) => {
+                            let index = self.widgets.children.get(host).map_or(0, Vec::len);
+                            self.insert_children(host, index, &children);
+                        }
+                        None => self.insert_top_level(self.top_level.len(), &children),
                     }
                 }
                 dioxus_core::DomEdit::AppendChildren { .. } => {
@@ -83,79 +358,77 @@ impl Renderer {
                 }
                 dioxus_core::DomEdit::ReplaceWith { root, m } => {
                     let replace_key = self.roots[&root];
-                    let parent = self.widgets.layout_parent.remove(replace_key);
-                    if let (Some(widget), Some(parent_widget)) = (
-                        self.widgets.gtk.remove(replace_key),
-                        parent.and_then(|key| self.widgets.layout_root.get(key)),
-                    ) {
-                        parent_widget.remove(&widget.upcast());
-                    }
-                    if let (Some(child_node), Some(parent_node)) = (
-                        self.widgets.taffy.remove(replace_key),
-                        parent.and_then(|key| self.widgets.taffy.get(key)),
-                    ) {
-                        self.taffy.remove_child(*parent_node, child_node).unwrap();
-                    }
-                    self.widgets.main.remove(replace_key);
-                    self.widgets.layout_root.remove(replace_key);
-                    for child_root in stack.drain(stack.len() - m as usize..) {
-                        let child_key = self.roots[&child_root];
-                        if let Some(parent) = parent {
-                            self.widgets.layout_parent.insert(child_key, parent);
-                            if let (Some(parent_node), Some(child_node)) = (
-                                self.widgets.taffy.get(parent),
-                                self.widgets.taffy.get(child_key),
-                            ) {
-                                self.taffy.add_child(*parent_node, *child_node).unwrap();
-                            }
-
-                            if let (Some(parent_layout), Some(child_widget)) = (
-                                self.widgets.layout_root.get(parent),
-                                self.widgets.gtk.get(child_key),
-                            ) {
-                                parent_layout.put(&child_widget.upcast(), 0., 0.);
-                            }
-                        }
-                    }
+                    let (parent, index) = self.position(replace_key);
+                    self.remove_element(replace_key);
+                    let children = stack
+                        .drain(stack.len() - m as usize..)
+                        .map(|child_root| self.roots[&child_root])
+                        .collect::<Vec<_>>();
+                    self.insert_siblings(parent, index, &children);
+                }
+                dioxus_core::DomEdit::InsertAfter { root, n } => {
+                    let (parent, index) = self.position(self.roots[&root]);
+                    let children = stack
+                        .drain(stack.len() - n as usize..)
+                        .map(|child_root| self.roots[&child_root])
+                        .collect::<Vec<_>>();
+                    self.insert_siblings(parent, index + 1, &children);
+                }
+                dioxus_core::DomEdit::InsertBefore { root, n } => {
+                    let (parent, index) = self.position(self.roots[&root]);
+                    let children = stack
+                        .drain(stack.len() - n as usize..)
+                        .map(|child_root| self.roots[&child_root])
+                        .collect::<Vec<_>>();
+                    self.insert_siblings(parent, index, &children);
+                }
+                dioxus_core::DomEdit::Remove { root } => {
+                    self.remove_element(self.roots[&root]);
+                }
+                dioxus_core::DomEdit::CreateTextNode { root, text } => {
+                    let key = self.widgets.main.insert(());
+                    self.roots.insert(root, key);
+                    self.widgets.ids.insert(key, root);
+                    let label = Label::builder()
+                        .label(text)
+                        .wrap(true)
+                        .valign(gtk::Align::Start)
+                        .build();
+                    self.widgets
+                        .gtk
+                        .insert(key, NativeWidget::Text(label.clone()));
+                    self.insert_leaf(key, label.upcast());
+                    stack.push(root);
                 }
-                dioxus_core::DomEdit::InsertAfter { .. } => todo!(),
-                dioxus_core::DomEdit::InsertBefore { .. } => todo!(),
-                dioxus_core::DomEdit::Remove { .. } => todo!(),
-                dioxus_core::DomEdit::CreateTextNode { .. } => todo!(),
                 dioxus_core::DomEdit::CreateElement { root, tag } => {
                     let key = self.widgets.main.insert(());
                     self.roots.insert(root, key);
+                    self.widgets.ids.insert(key, root);
                     match tag {
                         "gtk_box" => {
+                            let taffy_node =
+                                self.taffy().new_node(Default::default(), &[]).unwrap();
                             let gtk_box = gtk::Box::default();
+                            gtk_box.set_layout_manager(Some(&TaffyLayout::new(
+                                self.layout.clone(),
+                                taffy_node,
+                            )));
                             self.widgets
                                 .gtk
                                 .insert(key, NativeWidget::View(gtk_box.clone()));
-
-                            let layout_root = Fixed::builder().hexpand(true).vexpand(true).build();
-                            gtk_box.append(&layout_root);
-                            self.widgets.layout_root.insert(key, layout_root);
-                            let taffy_node = self.taffy.new_node(Default::default(), &[]).unwrap();
+                            self.widgets.layout_root.insert(key, gtk_box.clone());
                             self.widgets.taffy.insert(key, taffy_node);
-                            self.taffy_nodes.insert(taffy_node, key);
+                            self.layout
+                                .borrow_mut()
+                                .widgets
+                                .insert(taffy_node, gtk_box.upcast());
                         }
                         "gtk_button" => {
                             let button = gtk::Button::builder().valign(gtk::Align::Start).build();
                             self.widgets
                                 .gtk
                                 .insert(key, NativeWidget::Button(button.clone()));
-                            let taffy_node = self
-                                .taffy
-                                .new_leaf(
-                                    Default::default(),
-                                    Boxed(Box::new(move |_| Size {
-                                        width: button.allocated_width() as f32,
-                                        height: button.allocated_height() as f32,
-                                    })),
-                                )
-                                .unwrap();
-                            self.widgets.taffy.insert(key, taffy_node);
-                            self.taffy_nodes.insert(taffy_node, key);
+                            self.insert_leaf(key, button.upcast());
                         }
                         "gtk_text_field" => {
                             let text_field =
@@ -164,61 +437,257 @@ impl Renderer {
                             self.widgets
                                 .gtk
                                 .insert(key, NativeWidget::TextField(text_field.clone()));
-                            let taffy_node = self
-                                .taffy
-                                .new_leaf(
-                                    Default::default(),
-                                    Boxed(Box::new(move |_| Size {
-                                        width: text_field.allocated_width() as f32,
-                                        height: text_field.allocated_height() as f32,
-                                    })),
-                                )
-                                .unwrap();
-                            self.widgets.taffy.insert(key, taffy_node);
-                            self.taffy_nodes.insert(taffy_node, key);
+                            self.insert_leaf(key, text_field.upcast());
                         }
                         "gtk_label" => {
-                            let label = Label::builder().valign(gtk::Align::Start).build();
+                            let label = Label::builder()
+                                .wrap(true)
+                                .valign(gtk::Align::Start)
+                                .build();
                             self.widgets
                                 .gtk
                                 .insert(key, NativeWidget::Text(label.clone()));
-                            let taffy_node = self
-                                .taffy
-                                .new_leaf(
-                                    Default::default(),
-                                    Boxed(Box::new(move |_| Size {
-                                        width: label.allocated_width() as f32,
-                                        height: label.allocated_height() as f32,
-                                    })),
+                            self.insert_leaf(key, label.upcast());
+                        }
+                        "gtk_check_box" => {
+                            let check_box = gtk::CheckButton::builder()
+                                .valign(gtk::Align::Start)
+                                .build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::CheckBox(check_box.clone()));
+                            self.insert_leaf(key, check_box.upcast());
+                        }
+                        "gtk_switch" => {
+                            let switch = gtk::Switch::builder().valign(gtk::Align::Start).build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::Switch(switch.clone()));
+                            self.insert_leaf(key, switch.upcast());
+                        }
+                        "gtk_toggle_button" => {
+                            let toggle_button = gtk::ToggleButton::builder()
+                                .valign(gtk::Align::Start)
+                                .build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::ToggleButton(toggle_button.clone()));
+                            self.insert_leaf(key, toggle_button.upcast());
+                        }
+                        "gtk_slider" => {
+                            let slider = gtk::Scale::builder()
+                                .orientation(Orientation::Horizontal)
+                                .adjustment(&default_adjustment())
+                                .width_request(150)
+                                .valign(gtk::Align::Start)
+                                .build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::Slider(slider.clone()));
+                            self.insert_leaf(key, slider.upcast());
+                        }
+                        "gtk_spin_button" => {
+                            let spin_button = gtk::SpinButton::builder()
+                                .adjustment(&default_adjustment())
+                                .valign(gtk::Align::Start)
+                                .build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::SpinButton(spin_button.clone()));
+                            self.insert_leaf(key, spin_button.upcast());
+                        }
+                        "gtk_dropdown" => {
+                            let dropdown = gtk::DropDown::builder()
+                                .model(&StringList::new(&[]))
+                                .selected(gtk::INVALID_LIST_POSITION)
+                                .valign(gtk::Align::Start)
+                                .build();
+                            // Its natural width follows the selected option
+                            let sender = self.sender.clone();
+                            dropdown.connect_selected_notify(move |_| {
+                                let _ =
+                                    sender.unbounded_send(MainEvent::InvalidateLayout(Some(key)));
+                            });
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::Dropdown(dropdown.clone()));
+                            self.insert_leaf(key, dropdown.upcast());
+                        }
+                        "gtk_text_area" => {
+                            let text_area =
+                                gtk::TextView::builder().valign(gtk::Align::Start).build();
+                            // Unlike an entry, the text view grows with its text
+                            let sender = self.sender.clone();
+                            text_area.buffer().connect_changed(move |_| {
+                                let _ =
+                                    sender.unbounded_send(MainEvent::InvalidateLayout(Some(key)));
+                            });
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::TextArea(text_area.clone()));
+                            self.insert_leaf(key, text_area.upcast());
+                        }
+                        "gtk_image" => {
+                            let picture = gtk::Picture::builder().valign(gtk::Align::Start).build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::Image(picture.clone()));
+                            self.track_natural_size(key, &picture);
+                            let measured = picture.clone();
+                            self.insert_measured_leaf(key, picture.upcast(), move |known| {
+                                measure_picture(&measured, known)
+                            });
+                        }
+                        "gtk_canvas" => {
+                            let canvas = gtk::DrawingArea::new();
+                            // Reports every button, rather than only the primary one
+                            let click = gtk::GestureClick::builder().button(0).build();
+                            canvas.add_controller(&click);
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::Canvas(canvas.clone()));
+                            self.widgets.clicks.insert(key, click);
+                            self.insert_leaf(key, canvas.upcast());
+                        }
+                        "gtk_progress_bar" => {
+                            let progress_bar = gtk::ProgressBar::builder()
+                                .valign(gtk::Align::Start)
+                                .build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::ProgressBar(progress_bar.clone()));
+                            self.insert_leaf(key, progress_bar.upcast());
+                        }
+                        "gtk_spinner" => {
+                            let spinner = gtk::Spinner::builder().valign(gtk::Align::Start).build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::Spinner(spinner.clone()));
+                            self.insert_leaf(key, spinner.upcast());
+                        }
+                        "gtk_level_bar" => {
+                            let level_bar =
+                                gtk::LevelBar::builder().valign(gtk::Align::Start).build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::LevelBar(level_bar.clone()));
+                            self.insert_leaf(key, level_bar.upcast());
+                        }
+                        "gtk_scroll_view" => {
+                            // Sized by its own style only, so it never grows to fit its content
+                            let taffy_node =
+                                self.taffy().new_node(Default::default(), &[]).unwrap();
+                            let scroll_view = gtk::ScrolledWindow::new();
+                            // Content is laid out after the offset is set, so an offset beyond
+                            // it is applied again as it grows
+                            let offsets: [Rc<Cell<Option<f64>>>; 2] = Default::default();
+                            for (adjustment, pending) in
+                                [scroll_view.hadjustment(), scroll_view.vadjustment()]
+                                    .into_iter()
+                                    .zip(offsets.clone())
+                            {
+                                adjustment.connect_changed(move |adjustment| {
+                                    scroll_to(adjustment, &pending, pending.get());
+                                });
+                            }
+                            self.widgets.scroll_to.insert(key, offsets);
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::ScrollView(scroll_view.clone()));
+                            self.widgets.taffy.insert(key, taffy_node);
+                            self.layout
+                                .borrow_mut()
+                                .widgets
+                                .insert(taffy_node, scroll_view.clone().upcast());
+                            // The content is the root of a separate layout that is measured
+                            // without bounds, which gives the scrolled window its scrollable size
+                            let content_node = self
+                                .taffy()
+                                .new_node(
+                                    Style {
+                                        flex_direction: FlexDirection::Column,
+                                        ..Default::default()
+                                    },
+                                    &[],
                                 )
                                 .unwrap();
+                            let content = gtk::Box::default();
+                            content.set_layout_manager(Some(&TaffyLayout::new(
+                                self.layout.clone(),
+                                content_node,
+                            )));
+                            scroll_view.set_child(Some(&content));
+                            self.widgets.layout_root.insert(key, content);
+                            self.widgets.layout_root_taffy.insert(key, content_node);
+                        }
+                        "gtk_list" => {
+                            // Sized by its own style like a scroll view, rows are laid out by
+                            // the list view as they scroll into sight
+                            let taffy_node =
+                                self.taffy().new_node(Default::default(), &[]).unwrap();
+                            let list = ListState::new(&self.app, &self.layout, &self.lifecycle);
+                            let scroll_view = gtk::ScrolledWindow::builder()
+                                .hscrollbar_policy(PolicyType::Never)
+                                .child(&list.view)
+                                .build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::List(scroll_view.clone()));
+                            self.widgets.lists.insert(key, list);
+                            self.widgets.taffy.insert(key, taffy_node);
+                            self.layout
+                                .borrow_mut()
+                                .widgets
+                                .insert(taffy_node, scroll_view.upcast());
+                        }
+                        "gtk_table" => {
+                            let taffy_node =
+                                self.taffy().new_node(Default::default(), &[]).unwrap();
+                            let table = TableState::new(&self.app, &self.layout, &self.lifecycle);
+                            let scroll_view =
+                                gtk::ScrolledWindow::builder().child(&table.view).build();
+                            self.widgets
+                                .gtk
+                                .insert(key, NativeWidget::Table(scroll_view.clone()));
+                            self.widgets.tables.insert(key, table);
                             self.widgets.taffy.insert(key, taffy_node);
-                            self.taffy_nodes.insert(taffy_node, key);
+                            self.layout
+                                .borrow_mut()
+                                .widgets
+                                .insert(taffy_node, scroll_view.upcast());
                         }
                         "gtk_window" => {
                             let window =
                                 ApplicationWindow::builder().application(&self.app).build();
+                            // Only the window the application opens with, later ones pick their own
+                            if let Some((width, height)) = self.default_size.take() {
+                                window.set_default_size(width, height);
+                            }
                             self.widgets
                                 .gtk
                                 .insert(key, NativeWidget::Window(window.clone()));
-                            let layout_root = Fixed::builder().hexpand(true).vexpand(true).build();
+                            let taffy_node = self.taffy().new_node(window_style(), &[]).unwrap();
+                            // The window's node is the root of its layout, computed by the
+                            // container whenever GTK allocates the window
+                            let layout_root = gtk::Box::default();
+                            layout_root.set_layout_manager(Some(&TaffyLayout::new(
+                                self.layout.clone(),
+                                taffy_node,
+                            )));
                             window.set_child(Some(&layout_root));
+                            let close_request: Rc<RefCell<Option<CloseRequestFn>>> =
+                                Default::default();
+                            let handler = close_request.clone();
+                            window.connect_close_request(move |_| {
+                                let handler = handler.borrow().clone();
+                                match handler {
+                                    Some(handler) => Inhibit(handler()),
+                                    None => Inhibit(false),
+                                }
+                            });
+                            self.widgets.close_request.insert(key, close_request);
                             self.widgets.layout_root.insert(key, layout_root);
-                            let taffy_node = self
-                                .taffy
-                                .new_node(
-                                    Style {
-                                        size: Size {
-                                            width: Dimension::Percent(1.),
-                                            height: Dimension::Percent(1.),
-                                        },
-                                        ..Default::default()
-                                    },
-                                    &[],
-                                )
-                                .unwrap();
                             self.widgets.taffy.insert(key, taffy_node);
-                            self.taffy_nodes.insert(taffy_node, key);
                         }
                         _ => todo!("Have not built tag {} yet", tag),
                     };
@@ -228,6 +697,7 @@ impl Renderer {
                 dioxus_core::DomEdit::CreatePlaceholder { root } => {
                     let key = self.widgets.main.insert(());
                     self.roots.insert(root, key);
+                    self.widgets.ids.insert(key, root);
                     stack.push(root);
                 }
                 dioxus_core::DomEdit::NewEventListener {
@@ -236,10 +706,10 @@ impl Renderer {
                     root,
                 } => {
                     let key = self.roots[&root];
-                    match (&self.widgets.gtk[key], event_name) {
+                    let handler_ids = match (&self.widgets.gtk[key], event_name) {
                         (NativeWidget::Button(widget), "press") => {
                             let sender = self.sender.clone();
-                            widget.connect_clicked(move |_| {
+                            let handler_id = widget.connect_clicked(move |_| {
                                 sender
                                     .unbounded_send(MainEvent::UserEvent(UserEvent {
                                         scope_id: Some(scope),
@@ -250,10 +720,11 @@ impl Renderer {
                                     }))
                                     .unwrap();
                             });
+                            vec![(widget.clone().upcast(), handler_id)]
                         }
                         (NativeWidget::TextField(widget), "text_change") => {
                             let sender = self.sender.clone();
-                            widget.connect_text_notify(move |field| {
+                            let handler_id = widget.connect_text_notify(move |field| {
                                 sender
                                     .unbounded_send(MainEvent::UserEvent(UserEvent {
                                         scope_id: Some(scope),
@@ -266,33 +737,346 @@ impl Renderer {
                                     }))
                                     .unwrap();
                             });
+                            vec![(widget.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::ScrollView(widget), "scroll") => {
+                            [widget.hadjustment(), widget.vadjustment()]
+                                .into_iter()
+                                .map(|adjustment| {
+                                    let sender = self.sender.clone();
+                                    let widget = widget.clone();
+                                    let handler_id = adjustment.connect_value_changed(move |_| {
+                                        let (horizontal, vertical) =
+                                            (widget.hadjustment(), widget.vadjustment());
+                                        sender
+                                            .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                                scope_id: Some(scope),
+                                                priority: EventPriority::High,
+                                                element: Some(ElementId(root as usize)),
+                                                name: event_name,
+                                                data: Arc::new(events::ScrollData {
+                                                    x: horizontal.value(),
+                                                    y: vertical.value(),
+                                                    content_width: horizontal.upper(),
+                                                    content_height: vertical.upper(),
+                                                    viewport_width: horizontal.page_size(),
+                                                    viewport_height: vertical.page_size(),
+                                                }),
+                                            }))
+                                            .unwrap();
+                                    });
+                                    (adjustment.upcast(), handler_id)
+                                })
+                                .collect()
+                        }
+                        (NativeWidget::Table(_), "select") => {
+                            let table = &self.widgets.tables[key];
+                            [
+                                table.single.clone().upcast::<gtk::SelectionModel>(),
+                                table.multi.clone().upcast(),
+                            ]
+                            .into_iter()
+                            .map(|model| {
+                                let sender = self.sender.clone();
+                                let handler_id =
+                                    model.connect_selection_changed(move |model, _, _| {
+                                        let selection = model.selection();
+                                        let rows = (0..selection.size())
+                                            .map(|nth| selection.nth(nth as u32) as usize)
+                                            .collect();
+                                        sender
+                                            .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                                scope_id: Some(scope),
+                                                priority: EventPriority::High,
+                                                element: Some(ElementId(root as usize)),
+                                                name: event_name,
+                                                data: Arc::new(events::SelectionData { rows }),
+                                            }))
+                                            .unwrap();
+                                    });
+                                (model.upcast(), handler_id)
+                            })
+                            .collect()
+                        }
+                        (NativeWidget::Table(_), "sort_change") => {
+                            let table = &self.widgets.tables[key];
+                            let sorter = table.view.sorter().unwrap();
+                            let sender = self.sender.clone();
+                            let view = table.view.clone();
+                            let probe = table.sort.clone();
+                            let handler_id = sorter.connect_changed(move |_, _| {
+                                let (column, ascending) = match probe.read(&view) {
+                                    Some((column, ascending)) => (Some(column), ascending),
+                                    None => (None, true),
+                                };
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::SortChangeData {
+                                            column,
+                                            ascending,
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(sorter.upcast(), handler_id)]
+                        }
+                        (NativeWidget::CheckBox(widget), "toggle") => {
+                            let sender = self.sender.clone();
+                            let handler_id = widget.connect_toggled(move |widget| {
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::ToggleData {
+                                            active: widget.is_active(),
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(widget.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::Switch(widget), "toggle") => {
+                            let sender = self.sender.clone();
+                            let handler_id = widget.connect_active_notify(move |widget| {
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::ToggleData {
+                                            active: widget.is_active(),
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(widget.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::ToggleButton(widget), "toggle") => {
+                            let sender = self.sender.clone();
+                            let handler_id = widget.connect_toggled(move |widget| {
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::ToggleData {
+                                            active: widget.is_active(),
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(widget.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::Slider(widget), "value_change") => {
+                            let sender = self.sender.clone();
+                            let handler_id = widget.connect_value_changed(move |widget| {
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::ValueChangeData {
+                                            value: widget.value(),
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(widget.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::SpinButton(widget), "value_change") => {
+                            let sender = self.sender.clone();
+                            let handler_id = widget.connect_value_changed(move |widget| {
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::ValueChangeData {
+                                            value: widget.value(),
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(widget.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::Dropdown(widget), "select") => {
+                            let sender = self.sender.clone();
+                            let handler_id = widget.connect_selected_notify(move |widget| {
+                                let index = Some(widget.selected())
+                                    .filter(|index| *index != gtk::INVALID_LIST_POSITION);
+                                let value = widget
+                                    .selected_item()
+                                    .and_then(|item| item.downcast::<gtk::StringObject>().ok())
+                                    .map(|item| item.string().into());
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::SelectData {
+                                            index: index.map(|index| index as usize),
+                                            value,
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(widget.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::TextArea(widget), "text_change") => {
+                            let sender = self.sender.clone();
+                            let buffer = widget.buffer();
+                            let handler_id = buffer.connect_changed(move |buffer| {
+                                let (start, end) = buffer.bounds();
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::TextChangeData {
+                                            value: buffer.text(&start, &end, false).into(),
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(buffer.upcast(), handler_id)]
+                        }
+                        (NativeWidget::TextArea(widget), "cursor_move") => {
+                            let sender = self.sender.clone();
+                            let buffer = widget.buffer();
+                            let handler_id = buffer.connect_cursor_position_notify(move |buffer| {
+                                let cursor = buffer.iter_at_offset(buffer.cursor_position());
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::CursorMoveData {
+                                            offset: cursor.offset() as usize,
+                                            line: cursor.line() as usize,
+                                            column: cursor.line_offset() as usize,
+                                        }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(buffer.upcast(), handler_id)]
+                        }
+                        (NativeWidget::Canvas(_), "pointer_down" | "pointer_up") => {
+                            let sender = self.sender.clone();
+                            let click = &self.widgets.clicks[key];
+                            let send = move |gesture: &gtk::GestureClick, x, y| {
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::PointerData {
+                                            x,
+                                            y,
+                                            button: gesture.current_button(),
+                                        }),
+                                    }))
+                                    .unwrap();
+                            };
+                            let handler_id = if event_name == "pointer_down" {
+                                click.connect_pressed(move |gesture, _, x, y| send(gesture, x, y))
+                            } else {
+                                click.connect_released(move |gesture, _, x, y| send(gesture, x, y))
+                            };
+                            vec![(click.clone().upcast(), handler_id)]
+                        }
+                        (NativeWidget::Canvas(widget), "pointer_move") => {
+                            let motion = gtk::EventControllerMotion::new();
+                            widget.add_controller(&motion);
+                            if let Some(previous) = self.widgets.motion.insert(key, motion.clone())
+                            {
+                                widget.remove_controller(&previous);
+                            }
+                            let sender = self.sender.clone();
+                            let handler_id = motion.connect_motion(move |_, x, y| {
+                                sender
+                                    .unbounded_send(MainEvent::UserEvent(UserEvent {
+                                        scope_id: Some(scope),
+                                        priority: EventPriority::High,
+                                        element: Some(ElementId(root as usize)),
+                                        name: event_name,
+                                        data: Arc::new(events::PointerData { x, y, button: 0 }),
+                                    }))
+                                    .unwrap();
+                            });
+                            vec![(motion.upcast(), handler_id)]
                         }
                         (_, evt) => todo!("Event not implemented for that component: {}", evt),
+                    };
+                    self.disconnect_handler(key, event_name);
+                    self.widgets
+                        .handlers
+                        .entry(key)
+                        .unwrap()
+                        .or_default()
+                        .insert(event_name, handler_ids);
+                }
+                dioxus_core::DomEdit::RemoveEventListener { root, event } => {
+                    let key = self.roots[&root];
+                    self.disconnect_handler(key, event);
+                    if let (Some(NativeWidget::Canvas(widget)), "pointer_move") =
+                        (self.widgets.gtk.get(key), event)
+                    {
+                        if let Some(motion) = self.widgets.motion.remove(key) {
+                            widget.remove_controller(&motion);
+                        }
+                    }
+                }
+                dioxus_core::DomEdit::SetText { root, text } => {
+                    let key = self.roots[&root];
+                    if let (NativeWidget::Text(widget), Some(taffy_node)) =
+                        (&self.widgets.gtk[key], self.widgets.taffy.get(key))
+                    {
+                        widget.set_text(text);
+                        self.taffy().mark_dirty(*taffy_node).unwrap();
                     }
                 }
-                dioxus_core::DomEdit::RemoveEventListener { .. } => todo!(),
-                dioxus_core::DomEdit::SetText { .. } => todo!(),
                 dioxus_core::DomEdit::SetAttribute {
                     root, field, value, ..
                 } => {
                     let key = self.roots[&root];
                     match (&self.widgets.gtk[key], self.widgets.taffy.get(key), field) {
                         (_, Some(taffy_node), "layout") => {
-                            let layout = serde_json::from_str(value).unwrap();
-                            self.taffy
-                                .set_style(*taffy_node, layout)
-                                .expect("failed to apply justify_content style");
+                            // A stale key belongs to a component that has since been dropped
+                            if let Some(layout) = values::get_value(value) {
+                                self.taffy()
+                                    .set_style(*taffy_node, layout)
+                                    .expect("failed to apply justify_content style");
+                            }
                         }
                         (NativeWidget::Text(ref widget), Some(taffy_node), "text") => {
                             widget.set_text(value);
-                            self.taffy.mark_dirty(*taffy_node).unwrap();
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
                         }
                         (NativeWidget::Window(widget), _, "title") => {
                             widget.set_title(Some(value));
                         }
+                        (NativeWidget::Window(_), _, "close_request") => {
+                            if let Some(CloseRequest(handler)) = values::get_value(value) {
+                                *self.widgets.close_request[key].borrow_mut() = Some(handler);
+                            }
+                        }
                         (NativeWidget::Button(widget), Some(taffy_node), "label") => {
                             widget.set_label(value);
-                            self.taffy.mark_dirty(*taffy_node).unwrap();
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
                         }
                         (NativeWidget::TextField(widget), _, "place_holder") => {
                             widget.set_placeholder_text(Some(value));
@@ -302,10 +1086,445 @@ impl Renderer {
                                 widget.set_text(value);
                             }
                         }
+                        (NativeWidget::ScrollView(widget), _, "horizontal_policy") => {
+                            widget.set_policy(scroll_policy(value), widget.policy().1);
+                        }
+                        (NativeWidget::ScrollView(widget), _, "vertical_policy") => {
+                            widget.set_policy(widget.policy().0, scroll_policy(value));
+                        }
+                        (NativeWidget::ScrollView(widget), _, "scroll_x") => {
+                            let pending = &self.widgets.scroll_to[key][0];
+                            scroll_to(&widget.hadjustment(), pending, value.parse().ok());
+                        }
+                        (NativeWidget::ScrollView(widget), _, "scroll_y") => {
+                            let pending = &self.widgets.scroll_to[key][1];
+                            scroll_to(&widget.vadjustment(), pending, value.parse().ok());
+                        }
+                        (NativeWidget::List(_), _, "item_count") => {
+                            self.widgets.lists[key].set_len(value.parse().unwrap());
+                        }
+                        (NativeWidget::CheckBox(widget), Some(taffy_node), "label") => {
+                            widget.set_label(Some(value));
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::CheckBox(widget), _, "group") => {
+                            let leader = self
+                                .widgets
+                                .groups
+                                .iter()
+                                .filter(|(other, group)| *other != key && *group == value)
+                                .find_map(|(other, _)| match self.widgets.gtk.get(other) {
+                                    Some(NativeWidget::CheckBox(leader)) => Some(leader.clone()),
+                                    _ => None,
+                                });
+                            widget.set_group(leader.as_ref());
+                            self.widgets.groups.insert(key, value.to_owned());
+                        }
+                        (NativeWidget::CheckBox(widget), _, "active") => {
+                            let active = value == "true";
+                            if widget.is_active() != active {
+                                // Checking a radio button unchecks the rest of its group
+                                self.without_events(&self.group_members(key), "toggle", || {
+                                    widget.set_active(active)
+                                });
+                            }
+                        }
+                        (NativeWidget::Switch(widget), _, "active") => {
+                            let active = value == "true";
+                            if widget.is_active() != active {
+                                self.without_events(&[key], "toggle", || widget.set_active(active));
+                            }
+                        }
+                        (NativeWidget::ToggleButton(widget), Some(taffy_node), "label") => {
+                            widget.set_label(value);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::ToggleButton(widget), _, "active") => {
+                            let active = value == "true";
+                            if widget.is_active() != active {
+                                self.without_events(&[key], "toggle", || widget.set_active(active));
+                            }
+                        }
+                        (
+                            NativeWidget::Slider(_) | NativeWidget::SpinButton(_),
+                            _,
+                            "min" | "max" | "step" | "value",
+                        ) => {
+                            let adjustment = self.widgets.gtk[key].adjustment().unwrap();
+                            let value = value.parse().unwrap();
+                            // Changing the bounds can clamp the value as well
+                            self.without_events(&[key], "value_change", || {
+                                set_adjustment(&adjustment, field, value)
+                            });
+                        }
+                        (NativeWidget::Slider(widget), Some(taffy_node), "digits") => {
+                            // Empty when the value is not shown
+                            match value.parse() {
+                                Ok(digits) => {
+                                    widget.set_digits(digits);
+                                    widget.set_draw_value(true);
+                                }
+                                Err(_) => {
+                                    widget.set_draw_value(false);
+                                    widget.set_digits(1);
+                                }
+                            }
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::SpinButton(widget), Some(taffy_node), "digits") => {
+                            widget.set_digits(value.parse().unwrap());
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Dropdown(widget), Some(taffy_node), "options") => {
+                            if let Some(options) = values::get_value::<Vec<String>>(value) {
+                                let list =
+                                    widget.model().unwrap().downcast::<StringList>().unwrap();
+                                // Removing or inserting options before the selected one moves
+                                // the selection, and the dropdown selects the first option
+                                // added to an empty one, so the selection from the props is
+                                // applied again
+                                let selected = self
+                                    .widgets
+                                    .selected
+                                    .get(key)
+                                    .copied()
+                                    .unwrap_or(gtk::INVALID_LIST_POSITION);
+                                self.without_events(&[key], "select", || {
+                                    update_options(&list, &options);
+                                    if widget.selected() != selected {
+                                        widget.set_selected(selected);
+                                    }
+                                });
+                                self.taffy().mark_dirty(*taffy_node).unwrap();
+                            }
+                        }
+                        (NativeWidget::Dropdown(widget), _, "selected") => {
+                            let selected = value.parse().unwrap();
+                            self.widgets.selected.insert(key, selected);
+                            if widget.selected() != selected {
+                                self.without_events(&[key], "select", || {
+                                    widget.set_selected(selected)
+                                });
+                            }
+                        }
+                        (NativeWidget::TextArea(widget), _, "value") => {
+                            let buffer = widget.buffer();
+                            let (start, end) = buffer.bounds();
+                            if value != buffer.text(&start, &end, false).as_str() {
+                                // Replacing the text moves the cursor as well
+                                self.without_events(&[key], "cursor_move", || {
+                                    self.without_events(&[key], "text_change", || {
+                                        buffer.set_text(value)
+                                    })
+                                });
+                            }
+                        }
+                        (NativeWidget::TextArea(widget), Some(taffy_node), "wrap") => {
+                            widget.set_wrap_mode(wrap_mode(value));
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::TextArea(widget), _, "editable") => {
+                            widget.set_editable(value == "true");
+                            widget.set_cursor_visible(value == "true");
+                        }
+                        (NativeWidget::TextArea(widget), Some(taffy_node), "monospace") => {
+                            widget.set_monospace(value == "true");
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Image(widget), Some(taffy_node), "source") => {
+                            if let Some(source) = values::get_value(value) {
+                                set_image_source(widget, &source);
+                                self.taffy().mark_dirty(*taffy_node).unwrap();
+                            }
+                        }
+                        (NativeWidget::Image(widget), _, "keep_aspect") => {
+                            widget.set_keep_aspect_ratio(value == "true");
+                        }
+                        (NativeWidget::Canvas(widget), _, "draw") => {
+                            if let Some(Draw(draw)) = values::get_value(value) {
+                                widget.set_draw_func(move |_, context, width, height| {
+                                    draw(context, width, height)
+                                });
+                                widget.queue_draw();
+                            }
+                        }
+                        (NativeWidget::ProgressBar(widget), _, "fraction") => {
+                            widget.set_fraction(value.parse().unwrap());
+                        }
+                        (NativeWidget::ProgressBar(widget), _, "pulse") => {
+                            // Empty when the bar does not pulse. Dioxus sets every attribute again
+                            // when their number changes, so the count may be the same.
+                            match value.parse() {
+                                Ok(pulse) => {
+                                    let pulsed = match self.widgets.pulses.insert(key, pulse) {
+                                        Some(previous) => pulse > previous,
+                                        None => true,
+                                    };
+                                    if pulsed {
+                                        widget.pulse();
+                                    }
+                                }
+                                Err(_) => {
+                                    self.widgets.pulses.remove(key);
+                                }
+                            }
+                        }
+                        (NativeWidget::ProgressBar(widget), Some(taffy_node), "show_text") => {
+                            widget.set_show_text(value == "true");
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::ProgressBar(widget), Some(taffy_node), "text") => {
+                            widget.set_text(Some(value).filter(|text| !text.is_empty()));
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Spinner(widget), _, "spinning") => {
+                            widget.set_spinning(value == "true");
+                        }
+                        // GTK rejects negative bounds with a critical
+                        (NativeWidget::LevelBar(widget), _, "min") => {
+                            widget.set_min_value(value.parse::<f64>().unwrap().max(0.));
+                        }
+                        (NativeWidget::LevelBar(widget), _, "max") => {
+                            widget.set_max_value(value.parse::<f64>().unwrap().max(0.));
+                        }
+                        (NativeWidget::LevelBar(widget), _, "value") => {
+                            widget.set_value(value.parse().unwrap());
+                        }
+                        (NativeWidget::LevelBar(widget), _, "offsets") => {
+                            for name in self.widgets.offsets.remove(key).unwrap_or_default() {
+                                widget.remove_offset_value(Some(name.as_str()));
+                            }
+                            if let Some(offsets) = values::get_value::<Vec<(String, f64)>>(value) {
+                                for (name, offset) in &offsets {
+                                    widget.add_offset_value(name, *offset);
+                                }
+                                self.widgets.offsets.insert(
+                                    key,
+                                    offsets.into_iter().map(|(name, _)| name).collect(),
+                                );
+                            }
+                        }
+                        (NativeWidget::Table(_), _, "item_count") => {
+                            self.widgets.tables[key].set_len(value.parse().unwrap());
+                        }
+                        (NativeWidget::Table(_), _, "columns") => {
+                            if let Some(columns) = values::get_value(value) {
+                                self.widgets.tables[key].set_columns(columns);
+                            }
+                        }
+                        (NativeWidget::Table(_), _, "items") => {
+                            if let Some(items) = values::get_value(value) {
+                                self.widgets.tables[key].set_items(items);
+                            }
+                        }
+                        (NativeWidget::Table(_), _, "selection_mode") => {
+                            self.widgets.tables[key].set_selection_mode(value);
+                        }
+                        (NativeWidget::List(_), _, "source") => {
+                            // A stale key belongs to a component that has since been dropped
+                            if let Some(source) = values::get_value(value) {
+                                self.widgets.lists[key].set_source(Some(source));
+                            }
+                        }
                         _ => todo!(),
                     };
                 }
-                dioxus_core::DomEdit::RemoveAttribute { .. } => todo!(),
+                dioxus_core::DomEdit::RemoveAttribute { root, name, .. } => {
+                    let key = self.roots[&root];
+                    match (&self.widgets.gtk[key], self.widgets.taffy.get(key), name) {
+                        (NativeWidget::Window(_), Some(taffy_node), "layout") => {
+                            self.taffy().set_style(*taffy_node, window_style()).unwrap();
+                        }
+                        (_, Some(taffy_node), "layout") => {
+                            self.taffy()
+                                .set_style(*taffy_node, Style::default())
+                                .unwrap();
+                        }
+                        (NativeWidget::Text(ref widget), Some(taffy_node), "text") => {
+                            widget.set_text("");
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Window(widget), _, "title") => {
+                            widget.set_title(None);
+                        }
+                        (NativeWidget::Window(_), _, "close_request") => {
+                            *self.widgets.close_request[key].borrow_mut() = None;
+                        }
+                        (NativeWidget::Button(widget), Some(taffy_node), "label") => {
+                            widget.set_label("");
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::TextField(widget), _, "place_holder") => {
+                            widget.set_placeholder_text(None);
+                        }
+                        (NativeWidget::TextField(widget), _, "value") => {
+                            if !widget.text().is_empty() {
+                                widget.set_text("");
+                            }
+                        }
+                        (NativeWidget::ScrollView(widget), _, "horizontal_policy") => {
+                            widget.set_policy(PolicyType::Automatic, widget.policy().1);
+                        }
+                        (NativeWidget::ScrollView(widget), _, "vertical_policy") => {
+                            widget.set_policy(widget.policy().0, PolicyType::Automatic);
+                        }
+                        // Offsets are left where the user scrolled to
+                        (NativeWidget::ScrollView(_), _, "scroll_x" | "scroll_y") => {
+                            let axis = if name == "scroll_x" { 0 } else { 1 };
+                            self.widgets.scroll_to[key][axis].set(None);
+                        }
+                        (NativeWidget::List(_), _, "item_count") => {
+                            self.widgets.lists[key].set_len(0);
+                        }
+                        (NativeWidget::List(_), _, "source") => {
+                            self.widgets.lists[key].set_source(None);
+                        }
+                        (NativeWidget::CheckBox(widget), Some(taffy_node), "label") => {
+                            widget.set_label(None);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::CheckBox(widget), _, "group") => {
+                            widget.set_group(None::<&gtk::CheckButton>);
+                            self.widgets.groups.remove(key);
+                        }
+                        (NativeWidget::CheckBox(widget), _, "active") => {
+                            if widget.is_active() {
+                                self.without_events(&[key], "toggle", || widget.set_active(false));
+                            }
+                        }
+                        (NativeWidget::Switch(widget), _, "active") => {
+                            if widget.is_active() {
+                                self.without_events(&[key], "toggle", || widget.set_active(false));
+                            }
+                        }
+                        (NativeWidget::ToggleButton(widget), Some(taffy_node), "label") => {
+                            widget.set_label("");
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::ToggleButton(widget), _, "active") => {
+                            if widget.is_active() {
+                                self.without_events(&[key], "toggle", || widget.set_active(false));
+                            }
+                        }
+                        // Dioxus removes every attribute when their number changes, before
+                        // setting the new ones
+                        (
+                            NativeWidget::Slider(_) | NativeWidget::SpinButton(_),
+                            _,
+                            "min" | "max" | "step" | "value",
+                        ) => {
+                            let adjustment = self.widgets.gtk[key].adjustment().unwrap();
+                            let default = default_adjustment();
+                            let value = match name {
+                                "min" => default.lower(),
+                                "max" => default.upper(),
+                                "step" => default.step_increment(),
+                                _ => adjustment.lower(),
+                            };
+                            self.without_events(&[key], "value_change", || {
+                                set_adjustment(&adjustment, name, value)
+                            });
+                        }
+                        (NativeWidget::Slider(widget), Some(taffy_node), "digits") => {
+                            widget.set_draw_value(false);
+                            widget.set_digits(1);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::SpinButton(widget), Some(taffy_node), "digits") => {
+                            widget.set_digits(0);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Dropdown(widget), Some(taffy_node), "options") => {
+                            let list = widget.model().unwrap().downcast::<StringList>().unwrap();
+                            self.without_events(&[key], "select", || update_options(&list, &[]));
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Dropdown(widget), _, "selected") => {
+                            self.widgets.selected.remove(key);
+                            self.without_events(&[key], "select", || {
+                                widget.set_selected(gtk::INVALID_LIST_POSITION)
+                            });
+                        }
+                        (NativeWidget::TextArea(widget), _, "value") => {
+                            let buffer = widget.buffer();
+                            if buffer.char_count() > 0 {
+                                self.without_events(&[key], "cursor_move", || {
+                                    self.without_events(&[key], "text_change", || {
+                                        buffer.set_text("")
+                                    })
+                                });
+                            }
+                        }
+                        (NativeWidget::TextArea(widget), Some(taffy_node), "wrap") => {
+                            widget.set_wrap_mode(WrapMode::None);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::TextArea(widget), _, "editable") => {
+                            widget.set_editable(true);
+                            widget.set_cursor_visible(true);
+                        }
+                        (NativeWidget::TextArea(widget), Some(taffy_node), "monospace") => {
+                            widget.set_monospace(false);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Image(widget), Some(taffy_node), "source") => {
+                            widget.set_paintable(None::<&gdk::Paintable>);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Image(widget), _, "keep_aspect") => {
+                            widget.set_keep_aspect_ratio(true);
+                        }
+                        (NativeWidget::Canvas(widget), _, "draw") => {
+                            widget.set_draw_func(|_, _, _, _| {});
+                            widget.queue_draw();
+                        }
+                        (NativeWidget::ProgressBar(widget), _, "fraction") => {
+                            widget.set_fraction(0.);
+                        }
+                        // The count is kept, as it is removed before being set again when the
+                        // number of attributes changes
+                        (NativeWidget::ProgressBar(_), _, "pulse") => {}
+                        (NativeWidget::ProgressBar(widget), Some(taffy_node), "show_text") => {
+                            widget.set_show_text(false);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::ProgressBar(widget), Some(taffy_node), "text") => {
+                            widget.set_text(None);
+                            self.taffy().mark_dirty(*taffy_node).unwrap();
+                        }
+                        (NativeWidget::Spinner(widget), _, "spinning") => {
+                            widget.set_spinning(false);
+                        }
+                        (NativeWidget::LevelBar(widget), _, "min") => {
+                            widget.set_min_value(0.);
+                        }
+                        (NativeWidget::LevelBar(widget), _, "max") => {
+                            widget.set_max_value(1.);
+                        }
+                        (NativeWidget::LevelBar(widget), _, "value") => {
+                            widget.set_value(widget.min_value());
+                        }
+                        (NativeWidget::LevelBar(widget), _, "offsets") => {
+                            for name in self.widgets.offsets.remove(key).unwrap_or_default() {
+                                widget.remove_offset_value(Some(name.as_str()));
+                            }
+                        }
+                        (NativeWidget::Table(_), _, "item_count") => {
+                            self.widgets.tables[key].set_len(0);
+                        }
+                        (NativeWidget::Table(_), _, "columns") => {
+                            self.widgets.tables[key].set_columns(Vec::new());
+                        }
+                        (NativeWidget::Table(_), _, "items") => {
+                            self.widgets.tables[key].set_items(Default::default());
+                        }
+                        (NativeWidget::Table(_), _, "selection_mode") => {
+                            self.widgets.tables[key].set_selection_mode("single");
+                        }
+                        // Attributes without a default to go back to
+                        _ => {}
+                    };
+                }
                 dioxus_core::DomEdit::PopRoot {} => {
                     stack.pop();
                 }
@@ -313,46 +1532,397 @@ impl Renderer {
         }
     }
 
-    pub fn recalculate_layout(&mut self) {
-        let key = self.roots[&1];
-        if let (Some(NativeWidget::Window(widget)), Some(taffy_node)) =
-            (self.widgets.gtk.get(key), self.widgets.taffy.get(key))
+    /// Inserts `children` into the ordered child list of `parent` at `index`, placing their
+    /// widgets in the parent's layout root and keeping the Taffy children in the same order.
+    fn insert_children(&mut self, parent: DefaultKey, index: usize, children: &[DefaultKey]) {
+        let layout_root = self
+            .widgets
+            .layout_root
+            .get(parent)
+            .expect("Trying to add a child to a component which does not have a layout root");
+        let siblings = self.widgets.children.entry(parent).unwrap().or_default();
+        // GTK keeps its own child order, used for focus and drawing
+        let mut previous = siblings[..index]
+            .iter()
+            .rev()
+            .find_map(|sibling| self.widgets.gtk.get(*sibling))
+            .map(NativeWidget::upcast);
+        for child_key in children {
+            self.widgets.layout_parent.insert(*child_key, parent);
+            if let Some(child_widget) = self.widgets.gtk.get(*child_key) {
+                let child_widget = child_widget.upcast();
+                layout_root.insert_child_after(&child_widget, previous.as_ref());
+                previous = Some(child_widget);
+            }
+        }
+        siblings.splice(index..index, children.iter().copied());
+        self.sync_taffy_children(parent);
+    }
+
+    /// Detaches `key` from its parent and frees it together with all of its descendants.
+    fn remove_element(&mut self, key: DefaultKey) {
+        if let Some(parent) = self.widgets.layout_parent.get(key).copied() {
+            let index = self.child_index(parent, key);
+            self.widgets.children[parent].remove(index);
+            if let (Some(widget), Some(layout_root)) = (
+                self.widgets.gtk.get(key),
+                self.widgets.layout_root.get(parent),
+            ) {
+                layout_root.remove(&widget.upcast());
+            }
+            self.sync_taffy_children(parent);
+        } else {
+            self.top_level.retain(|top_level_key| *top_level_key != key);
+            if let Some(NativeWidget::Window(window)) = self.widgets.gtk.get(key) {
+                window.destroy();
+            }
+        }
+        self.free_subtree(key);
+    }
+
+    /// Only the top of the subtree needs to be detached from GTK, its descendants go with it.
+    fn free_subtree(&mut self, key: DefaultKey) {
+        for child_key in self.widgets.children.remove(key).unwrap_or_default() {
+            self.free_subtree(child_key);
+        }
+        for (object, handler_id) in self
+            .widgets
+            .handlers
+            .remove(key)
+            .into_iter()
+            .flat_map(HashMap::into_values)
+            .flatten()
+        {
+            object.disconnect(handler_id);
+        }
+        if let Some(taffy_node) = self.widgets.taffy.remove(key) {
+            let mut layout = self.layout.borrow_mut();
+            layout.widgets.remove(&taffy_node);
+            layout.taffy.remove(taffy_node);
+        }
+        if let Some(taffy_node) = self.widgets.layout_root_taffy.remove(key) {
+            self.taffy().remove(taffy_node);
+        }
+        // Dioxus may already have handed the id to a newer element, which must stay mapped
+        if let Some(id) = self.widgets.ids.remove(key) {
+            if self.roots.get(&id) == Some(&key) {
+                self.roots.remove(&id);
+            }
+        }
+        self.widgets.gtk.remove(key);
+        self.widgets.close_request.remove(key);
+        self.widgets.lists.remove(key);
+        self.widgets.tables.remove(key);
+        self.widgets.scroll_to.remove(key);
+        self.widgets.groups.remove(key);
+        self.widgets.clicks.remove(key);
+        self.widgets.motion.remove(key);
+        self.widgets.selected.remove(key);
+        self.widgets.pulses.remove(key);
+        self.widgets.offsets.remove(key);
+        self.widgets.layout_root.remove(key);
+        self.widgets.layout_parent.remove(key);
+        self.widgets.main.remove(key);
+    }
+
+    fn disconnect_handler(&mut self, key: DefaultKey, event_name: &str) {
+        for (object, handler_id) in self
+            .widgets
+            .handlers
+            .get_mut(key)
+            .and_then(|handlers| handlers.remove(event_name))
+            .unwrap_or_default()
         {
-            self.taffy
-                .compute_layout(
-                    *taffy_node,
-                    Size {
-                        width: Number::Defined(widget.default_width() as f32),
-                        height: Number::Defined(widget.default_height() as f32),
-                    },
-                )
+            object.disconnect(handler_id);
+        }
+    }
+
+    /// Runs `update` with the `event_name` handlers of `keys` blocked, so a change made by the
+    /// renderer is not reported back as a user event.
+    fn without_events(&self, keys: &[DefaultKey], event_name: &str, update: impl FnOnce()) {
+        let handlers = keys
+            .iter()
+            .filter_map(|key| self.widgets.handlers.get(*key)?.get(event_name))
+            .flatten()
+            .collect::<Vec<_>>();
+        for (object, handler_id) in &handlers {
+            object.block_signal(handler_id);
+        }
+        update();
+        for (object, handler_id) in &handlers {
+            object.unblock_signal(handler_id);
+        }
+    }
+
+    /// Returns the check boxes in the radio group of `key`, or only `key` if it has none.
+    fn group_members(&self, key: DefaultKey) -> Vec<DefaultKey> {
+        match self.widgets.groups.get(key) {
+            Some(group) => self
+                .widgets
+                .groups
+                .iter()
+                .filter(|(_, other)| *other == group)
+                .map(|(other, _)| other)
+                .collect(),
+            None => vec![key],
+        }
+    }
+
+    /// Returns the parent of `key`, if it is not top-level, and its index among its siblings.
+    fn position(&self, key: DefaultKey) -> (Option<DefaultKey>, usize) {
+        match self.widgets.layout_parent.get(key) {
+            Some(parent) => (Some(*parent), self.child_index(*parent, key)),
+            None => (
+                None,
+                self.top_level
+                    .iter()
+                    .position(|top_level_key| *top_level_key == key)
+                    .expect("Component has neither a parent nor is it top-level"),
+            ),
+        }
+    }
+
+    fn insert_siblings(
+        &mut self,
+        parent: Option<DefaultKey>,
+        index: usize,
+        children: &[DefaultKey],
+    ) {
+        match parent {
+            Some(parent) => self.insert_children(parent, index, children),
+            None => self.insert_top_level(index, children),
+        }
+    }
+
+    fn insert_top_level(&mut self, index: usize, children: &[DefaultKey]) {
+        for child_key in children {
+            match self.widgets.gtk.get(*child_key) {
+                Some(NativeWidget::Window(window)) => window.present(),
+                // Placeholder for a window that is not currently shown
+                None => {}
+                Some(_) => unreachable!("Only Window can be a top-level component"),
+            }
+        }
+        self.top_level
+            .splice(index..index, children.iter().copied());
+    }
+
+    fn child_index(&self, parent: DefaultKey, child: DefaultKey) -> usize {
+        self.widgets.children[parent]
+            .iter()
+            .position(|key| *key == child)
+            .expect("Child is missing from its parent's children")
+    }
+
+    /// Placeholders have no Taffy node, so the Taffy children are rebuilt from the element
+    /// children rather than inserted by index.
+    fn sync_taffy_children(&mut self, parent: DefaultKey) {
+        let parent_node = self
+            .widgets
+            .layout_root_taffy
+            .get(parent)
+            .or_else(|| self.widgets.taffy.get(parent));
+        if let Some(parent_node) = parent_node {
+            let child_nodes = self
+                .widgets
+                .children
+                .get(parent)
+                .into_iter()
+                .flatten()
+                .filter_map(|child_key| self.widgets.taffy.get(*child_key).copied())
+                .collect::<Vec<_>>();
+            self.taffy()
+                .set_children(*parent_node, &child_nodes)
                 .unwrap();
-            self.apply_layout_changes();
         }
     }
-    fn apply_layout_changes(&mut self) {
-        let mut stack = vec![self.roots[&1]];
-        while let Some(node) = stack.pop() {
-            let taffy_node = self.widgets.taffy[node];
-            if let Ok(children) = self.taffy.children(taffy_node) {
-                for key in children.iter().map(|child| self.taffy_nodes[child]) {
-                    stack.push(key);
+
+    /// Leaves are measured from GTK, so anything that changes their natural size has to
+    /// invalidate their Taffy node.
+    fn track_natural_size(&self, key: DefaultKey, widget: &impl IsA<Widget>) {
+        for property in ["scale-factor", "css-classes", "has-focus"] {
+            let sender = self.sender.clone();
+            widget.connect_notify_local(Some(property), move |_, _| {
+                // Widgets of an embedded tree can outlive it until their container is disposed
+                let _ = sender.unbounded_send(MainEvent::InvalidateLayout(Some(key)));
+            });
+        }
+    }
+
+    /// Font, DPI and theme changes can resize every leaf.
+    pub fn track_settings(&self) {
+        if let Some(settings) = Settings::default() {
+            for property in [
+                "gtk-font-name",
+                "gtk-xft-dpi",
+                "gtk-theme-name",
+                "gtk-application-prefer-dark-theme",
+            ] {
+                let sender = self.sender.clone();
+                settings.connect_notify_local(Some(property), move |_, _| {
+                    sender
+                        .unbounded_send(MainEvent::InvalidateLayout(None))
+                        .unwrap();
+                });
+            }
+        }
+    }
+
+    /// Marks `key`, or every node if `None`, as dirty. GTK coalesces the resizes this queues
+    /// into a single layout pass on the next frame.
+    pub fn invalidate_layout(&mut self, key: Option<DefaultKey>) {
+        match key {
+            Some(key) => {
+                if let (Some(taffy_node), Some(widget)) =
+                    (self.widgets.taffy.get(key), self.widgets.gtk.get(key))
+                {
+                    self.taffy().mark_dirty(*taffy_node).unwrap();
+                    widget.upcast().queue_resize();
+                }
+            }
+            None => {
+                // The tree is shared, so this reaches the rows of lists and tables as well
+                let mut layout = self.layout.borrow_mut();
+                let LayoutTree { taffy, widgets } = &mut *layout;
+                for (taffy_node, widget) in widgets.iter() {
+                    taffy.mark_dirty(*taffy_node).unwrap();
+                    widget.queue_resize();
                 }
             }
-            let gtk_node = &self.widgets.gtk[node];
-            let layout = self.taffy.layout(taffy_node).unwrap();
-            if let Some(layout_root) = self
+        }
+    }
+
+    /// Nested containers are only allocated again if GTK is told they need it, even when
+    /// their own size did not change. Taffy marks the ancestors of a changed node dirty as well,
+    /// so containers that are still clean have nothing to lay out.
+    pub fn queue_layout(&self) {
+        for (key, layout_root) in &self.widgets.layout_root {
+            let taffy_node = self
                 .widgets
-                .layout_parent
-                .get(node)
-                .and_then(|parent| self.widgets.layout_root.get(*parent))
-            {
-                layout_root.move_(
-                    &gtk_node.upcast(),
-                    layout.location.x as f64,
-                    layout.location.y as f64,
-                );
+                .layout_root_taffy
+                .get(key)
+                .or_else(|| self.widgets.taffy.get(key));
+            if let Some(taffy_node) = taffy_node {
+                if self.taffy().dirty(*taffy_node).unwrap() {
+                    layout_root.queue_resize();
+                }
             }
         }
     }
+
+    /// Creates the container the top-level components are placed in, rather than being shown
+    /// as windows. It is the root of their layout.
+    pub fn host(&mut self) -> gtk::Box {
+        let key = self.widgets.main.insert(());
+        let taffy_node = self.taffy().new_node(Default::default(), &[]).unwrap();
+        let host = gtk::Box::default();
+        host.set_layout_manager(Some(&TaffyLayout::new(self.layout.clone(), taffy_node)));
+        self.widgets.layout_root.insert(key, host.clone());
+        self.widgets.taffy.insert(key, taffy_node);
+        self.layout
+            .borrow_mut()
+            .widgets
+            .insert(taffy_node, host.clone().upcast());
+        self.host = Some(key);
+        host
+    }
+
+    /// Frees the tree of an embedded renderer. Its container is detached by the owner.
+    pub fn close(&mut self) {
+        if let Some(host) = self.host.take() {
+            self.free_subtree(host);
+        }
+    }
+
+    fn insert_leaf(&mut self, key: DefaultKey, widget: Widget) {
+        self.track_natural_size(key, &widget);
+        let measured = widget.clone();
+        self.insert_measured_leaf(key, widget, move |known| measure_widget(&measured, known));
+    }
+
+    fn insert_measured_leaf(
+        &mut self,
+        key: DefaultKey,
+        widget: Widget,
+        measure: impl Fn(Size<Number>) -> Size<f32> + 'static,
+    ) {
+        use taffy::node::MeasureFunc::Boxed;
+        let taffy_node = self
+            .taffy()
+            .new_leaf(Default::default(), Boxed(Box::new(measure)))
+            .unwrap();
+        self.widgets.taffy.insert(key, taffy_node);
+        self.layout.borrow_mut().widgets.insert(taffy_node, widget);
+    }
+
+    fn taffy(&self) -> RefMut<Taffy> {
+        RefMut::map(self.layout.borrow_mut(), |layout| &mut layout.taffy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::options_splice;
+
+    fn options(options: &[&str]) -> Vec<String> {
+        options.iter().map(|option| option.to_string()).collect()
+    }
+
+    /// Returns the splice from `current` to `next`, after checking that applying it gives `next`.
+    fn splice(current: &[&str], next: &[&str]) -> (usize, usize, Vec<String>) {
+        let (current, next) = (options(current), options(next));
+        let (position, removed, added) = options_splice(&current, &next);
+        let mut spliced = current.clone();
+        spliced.splice(position..position + removed, added.iter().cloned());
+        assert_eq!(spliced, next);
+        (position, removed, added.to_vec())
+    }
+
+    #[test]
+    fn unchanged() {
+        assert_eq!(splice(&["a", "b"], &["a", "b"]), (2, 0, vec![]));
+    }
+
+    #[test]
+    fn insert() {
+        assert_eq!(
+            splice(&["a", "c"], &["a", "b", "c"]),
+            (1, 0, options(&["b"]))
+        );
+        assert_eq!(splice(&["b"], &["a", "b"]), (0, 0, options(&["a"])));
+        assert_eq!(splice(&["a"], &["a", "b"]), (1, 0, options(&["b"])));
+    }
+
+    #[test]
+    fn remove() {
+        assert_eq!(splice(&["a", "b", "c"], &["a", "c"]), (1, 1, vec![]));
+        assert_eq!(splice(&["a", "b", "c"], &["b", "c"]), (0, 1, vec![]));
+        assert_eq!(splice(&["a", "b", "c"], &["a", "b"]), (2, 1, vec![]));
+    }
+
+    #[test]
+    fn reorder() {
+        assert_eq!(
+            splice(&["a", "b", "c", "d"], &["a", "c", "b", "d"]),
+            (1, 2, options(&["c", "b"]))
+        );
+        assert_eq!(
+            splice(&["a", "b", "c"], &["c", "b", "a"]),
+            (0, 3, options(&["c", "b", "a"]))
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        assert_eq!(splice(&["a", "a", "a"], &["a", "a"]), (2, 1, vec![]));
+        assert_eq!(splice(&["a", "b", "a"], &["a", "a"]), (1, 1, vec![]));
+        assert_eq!(splice(&["a"], &["a", "a"]), (1, 0, options(&["a"])));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(splice(&[], &["a", "b"]), (0, 0, options(&["a", "b"])));
+        assert_eq!(splice(&["a", "b"], &[]), (0, 2, vec![]));
+        assert_eq!(splice(&[], &[]), (0, 0, vec![]));
+    }
 }
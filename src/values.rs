@@ -0,0 +1,55 @@
+use dioxus_core::ScopeState;
+use slotmap::{DefaultKey, Key, KeyData, SlotMap};
+use std::any::Any;
+use std::cell::RefCell;
+
+thread_local! {
+    /// Props of mounted components that can't be written as a string, such as styles. Their
+    /// attribute only carries the key.
+    static VALUES: RefCell<SlotMap<DefaultKey, Box<dyn Any>>> = RefCell::new(SlotMap::new());
+}
+
+struct ValueSlot {
+    key: Option<DefaultKey>,
+}
+
+impl Drop for ValueSlot {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            // The store may already be gone if the tree is dropped during thread teardown
+            let _ = VALUES.try_with(|values| values.borrow_mut().remove(key));
+        }
+    }
+}
+
+/// Stores `value` for the renderer and returns the value of its attribute.
+///
+/// The attribute only changes along with the value, so dioxus skips unchanged values when
+/// diffing.
+pub(crate) fn use_value<T: PartialEq + Clone + 'static>(
+    cx: &ScopeState,
+    value: Option<&T>,
+) -> Option<u64> {
+    let slot = cx.use_hook(|_| ValueSlot { key: None });
+    VALUES.with(|values| {
+        let mut values = values.borrow_mut();
+        match (slot.key, value) {
+            (Some(key), Some(value)) if values[key].downcast_ref() == Some(value) => {
+                Some(key.data().as_ffi())
+            }
+            (key, value) => {
+                if let Some(key) = key {
+                    values.remove(key);
+                }
+                slot.key = value.map(|value| values.insert(Box::new(value.clone())));
+                slot.key.map(|key| key.data().as_ffi())
+            }
+        }
+    })
+}
+
+/// Looks up the value behind an attribute written by [`use_value`].
+pub(crate) fn get_value<T: Clone + 'static>(value: &str) -> Option<T> {
+    let key: DefaultKey = KeyData::from_ffi(value.parse().ok()?).into();
+    VALUES.with(|values| values.borrow().get(key)?.downcast_ref().cloned())
+}